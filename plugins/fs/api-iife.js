@@ -1 +1,879 @@
-if("__TAURI__"in window){var __TAURI_PLUGIN_FS__=function(t){"use strict";function e(t,e,n,i){if("a"===n&&!i)throw new TypeError("Private accessor was defined without a getter");if("function"==typeof e?t!==e||!i:!e.has(t))throw new TypeError("Cannot read private member from an object whose class did not declare it");return"m"===n?i:"a"===n?i.call(t):i?i.value:e.get(t)}function n(t,e,n,i,o){if("function"==typeof e?t!==e||!o:!e.has(t))throw new TypeError("Cannot write private member to an object whose class did not declare it");return e.set(t,n),n}var i,o,r,a,s,c;"function"==typeof SuppressedError&&SuppressedError;class f{constructor(){this.__TAURI_CHANNEL_MARKER__=!0,i.set(this,(()=>{})),o.set(this,0),r.set(this,{}),this.id=function(t,e=!1){return window.__TAURI_INTERNALS__.transformCallback(t,e)}((({message:t,id:a})=>{if(a===e(this,o,"f")){n(this,o,a+1),e(this,i,"f").call(this,t);const s=Object.keys(e(this,r,"f"));if(s.length>0){let t=a+1;for(const n of s.sort()){if(parseInt(n)!==t)break;{const o=e(this,r,"f")[n];delete e(this,r,"f")[n],e(this,i,"f").call(this,o),t+=1}}n(this,o,t)}}else e(this,r,"f")[a.toString()]=t}))}set onmessage(t){n(this,i,t)}get onmessage(){return e(this,i,"f")}toJSON(){return`__CHANNEL__:${this.id}`}}async function l(t,e={},n){return window.__TAURI_INTERNALS__.invoke(t,e,n)}i=new WeakMap,o=new WeakMap,r=new WeakMap;class u{get rid(){return e(this,a,"f")}constructor(t){a.set(this,void 0),n(this,a,t)}async close(){return l("plugin:resources|close",{rid:this.rid})}}function p(t){return{isFile:t.isFile,isDirectory:t.isDirectory,isSymlink:t.isSymlink,size:t.size,mtime:null!==t.mtime?new Date(t.mtime):null,atime:null!==t.atime?new Date(t.atime):null,birthtime:null!==t.birthtime?new Date(t.birthtime):null,readonly:t.readonly,fileAttributes:t.fileAttributes,dev:t.dev,ino:t.ino,mode:t.mode,nlink:t.nlink,uid:t.uid,gid:t.gid,rdev:t.rdev,blksize:t.blksize,blocks:t.blocks}}a=new WeakMap,t.BaseDirectory=void 0,(s=t.BaseDirectory||(t.BaseDirectory={}))[s.Audio=1]="Audio",s[s.Cache=2]="Cache",s[s.Config=3]="Config",s[s.Data=4]="Data",s[s.LocalData=5]="LocalData",s[s.Document=6]="Document",s[s.Download=7]="Download",s[s.Picture=8]="Picture",s[s.Public=9]="Public",s[s.Video=10]="Video",s[s.Resource=11]="Resource",s[s.Temp=12]="Temp",s[s.AppConfig=13]="AppConfig",s[s.AppData=14]="AppData",s[s.AppLocalData=15]="AppLocalData",s[s.AppCache=16]="AppCache",s[s.AppLog=17]="AppLog",s[s.Desktop=18]="Desktop",s[s.Executable=19]="Executable",s[s.Font=20]="Font",s[s.Home=21]="Home",s[s.Runtime=22]="Runtime",s[s.Template=23]="Template",t.SeekMode=void 0,(c=t.SeekMode||(t.SeekMode={}))[c.Start=0]="Start",c[c.Current=1]="Current",c[c.End=2]="End";class w extends u{async read(t){if(0===t.byteLength)return 0;const e=await l("plugin:fs|read",{rid:this.rid,len:t.byteLength}),n=function(t){const e=new Uint8ClampedArray(t),n=e.byteLength;let i=0;for(let t=0;t<n;t++)i*=256,i+=e[t];return i}(e.slice(-8)),i=e instanceof ArrayBuffer?new Uint8Array(e):e;return t.set(i.slice(0,i.length-8)),0===n?null:n}async seek(t,e){return await l("plugin:fs|seek",{rid:this.rid,offset:t,whence:e})}async stat(){return p(await l("plugin:fs|fstat",{rid:this.rid}))}async truncate(t){await l("plugin:fs|ftruncate",{rid:this.rid,len:t})}async write(t){return await l("plugin:fs|write",{rid:this.rid,data:t})}}async function h(t){await l("plugin:fs|unwatch",{rid:t})}return t.FileHandle=w,t.copyFile=async function(t,e,n){if(t instanceof URL&&"file:"!==t.protocol||e instanceof URL&&"file:"!==e.protocol)throw new TypeError("Must be a file URL.");await l("plugin:fs|copy_file",{fromPath:t instanceof URL?t.toString():t,toPath:e instanceof URL?e.toString():e,options:n})},t.create=async function(t,e){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");const n=await l("plugin:fs|create",{path:t instanceof URL?t.toString():t,options:e});return new w(n)},t.exists=async function(t,e){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");return await l("plugin:fs|exists",{path:t instanceof URL?t.toString():t,options:e})},t.lstat=async function(t,e){return p(await l("plugin:fs|lstat",{path:t instanceof URL?t.toString():t,options:e}))},t.mkdir=async function(t,e){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");await l("plugin:fs|mkdir",{path:t instanceof URL?t.toString():t,options:e})},t.open=async function(t,e){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");const n=await l("plugin:fs|open",{path:t instanceof URL?t.toString():t,options:e});return new w(n)},t.readDir=async function(t,e){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");return await l("plugin:fs|read_dir",{path:t instanceof URL?t.toString():t,options:e})},t.readFile=async function(t,e){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");const n=await l("plugin:fs|read_file",{path:t instanceof URL?t.toString():t,options:e});return n instanceof ArrayBuffer?new Uint8Array(n):Uint8Array.from(n)},t.readTextFile=async function(t,e){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");return await l("plugin:fs|read_text_file",{path:t instanceof URL?t.toString():t,options:e})},t.readTextFileLines=async function(t,e){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");const n=t instanceof URL?t.toString():t;return await Promise.resolve({path:n,rid:null,async next(){null===this.rid&&(this.rid=await l("plugin:fs|read_text_file_lines",{path:n,options:e}));const[t,i]=await l("plugin:fs|read_text_file_lines_next",{rid:this.rid});return i&&(this.rid=null),{value:i?"":t,done:i}},[Symbol.asyncIterator](){return this}})},t.remove=async function(t,e){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");await l("plugin:fs|remove",{path:t instanceof URL?t.toString():t,options:e})},t.rename=async function(t,e,n){if(t instanceof URL&&"file:"!==t.protocol||e instanceof URL&&"file:"!==e.protocol)throw new TypeError("Must be a file URL.");await l("plugin:fs|rename",{oldPath:t instanceof URL?t.toString():t,newPath:e instanceof URL?e.toString():e,options:n})},t.stat=async function(t,e){return p(await l("plugin:fs|stat",{path:t instanceof URL?t.toString():t,options:e}))},t.truncate=async function(t,e,n){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");await l("plugin:fs|truncate",{path:t instanceof URL?t.toString():t,len:e,options:n})},t.watch=async function(t,e,n){const i={recursive:!1,delayMs:2e3,...n},o=Array.isArray(t)?t:[t];for(const t of o)if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");const r=new f;r.onmessage=e;const a=await l("plugin:fs|watch",{paths:o.map((t=>t instanceof URL?t.toString():t)),options:i,onEvent:r});return()=>{h(a)}},t.watchImmediate=async function(t,e,n){const i={recursive:!1,...n,delayMs:null},o=Array.isArray(t)?t:[t];for(const t of o)if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");const r=new f;r.onmessage=e;const a=await l("plugin:fs|watch",{paths:o.map((t=>t instanceof URL?t.toString():t)),options:i,onEvent:r});return()=>{h(a)}},t.writeFile=async function(t,e,n){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");await l("plugin:fs|write_file",e,{headers:{path:encodeURIComponent(t instanceof URL?t.toString():t),options:JSON.stringify(n)}})},t.writeTextFile=async function(t,e,n){if(t instanceof URL&&"file:"!==t.protocol)throw new TypeError("Must be a file URL.");const i=new TextEncoder;await l("plugin:fs|write_text_file",i.encode(e),{headers:{path:encodeURIComponent(t instanceof URL?t.toString():t),options:JSON.stringify(n)}})},t}({});Object.defineProperty(window.__TAURI__,"fs",{value:__TAURI_PLUGIN_FS__})}
+if ('__TAURI__' in window) {
+var __TAURI_PLUGIN_FS__ = (function (exports) {
+'use strict';
+function transformCallback(callback, once = false) {
+return window.__TAURI_INTERNALS__.transformCallback(callback, once)
+}
+class Channel {
+#onmessage = () => {}
+#nextMessageId = 0
+#pendingMessages = {}
+constructor() {
+this.__TAURI_CHANNEL_MARKER__ = true
+this.id = transformCallback(({ message, id }) => {
+if (id === this.#nextMessageId) {
+this.#nextMessageId = id + 1
+this.#onmessage(message)
+const pendingMessageIds = Object.keys(this.#pendingMessages)
+if (pendingMessageIds.length > 0) {
+let nextId = id + 1
+for (const pendingId of pendingMessageIds.sort()) {
+if (parseInt(pendingId) === nextId) {
+const message = this.#pendingMessages[pendingId]
+delete this.#pendingMessages[pendingId]
+this.#onmessage(message)
+nextId += 1
+} else {
+break
+}
+}
+this.#nextMessageId = nextId
+}
+} else {
+this.#pendingMessages[id.toString()] = message
+}
+})
+}
+set onmessage(handler) {
+this.#onmessage = handler
+}
+get onmessage() {
+return this.#onmessage
+}
+toJSON() {
+return `__CHANNEL__:${this.id}`
+}
+}
+async function invoke(cmd, args = {}, options) {
+return window.__TAURI_INTERNALS__.invoke(cmd, args, options)
+}
+class Resource {
+#rid
+get rid() {
+return this.#rid
+}
+constructor(rid) {
+this.#rid = rid
+}
+async close() {
+return invoke('plugin:resources|close', {
+rid: this.rid
+})
+}
+}
+var BaseDirectory;
+(function (BaseDirectory) {
+BaseDirectory[BaseDirectory["Audio"] = 1] = "Audio";
+BaseDirectory[BaseDirectory["Cache"] = 2] = "Cache";
+BaseDirectory[BaseDirectory["Config"] = 3] = "Config";
+BaseDirectory[BaseDirectory["Data"] = 4] = "Data";
+BaseDirectory[BaseDirectory["LocalData"] = 5] = "LocalData";
+BaseDirectory[BaseDirectory["Document"] = 6] = "Document";
+BaseDirectory[BaseDirectory["Download"] = 7] = "Download";
+BaseDirectory[BaseDirectory["Picture"] = 8] = "Picture";
+BaseDirectory[BaseDirectory["Public"] = 9] = "Public";
+BaseDirectory[BaseDirectory["Video"] = 10] = "Video";
+BaseDirectory[BaseDirectory["Resource"] = 11] = "Resource";
+BaseDirectory[BaseDirectory["Temp"] = 12] = "Temp";
+BaseDirectory[BaseDirectory["AppConfig"] = 13] = "AppConfig";
+BaseDirectory[BaseDirectory["AppData"] = 14] = "AppData";
+BaseDirectory[BaseDirectory["AppLocalData"] = 15] = "AppLocalData";
+BaseDirectory[BaseDirectory["AppCache"] = 16] = "AppCache";
+BaseDirectory[BaseDirectory["AppLog"] = 17] = "AppLog";
+BaseDirectory[BaseDirectory["Desktop"] = 18] = "Desktop";
+BaseDirectory[BaseDirectory["Executable"] = 19] = "Executable";
+BaseDirectory[BaseDirectory["Font"] = 20] = "Font";
+BaseDirectory[BaseDirectory["Home"] = 21] = "Home";
+BaseDirectory[BaseDirectory["Runtime"] = 22] = "Runtime";
+BaseDirectory[BaseDirectory["Template"] = 23] = "Template";
+})(BaseDirectory || (BaseDirectory = {}));
+
+var SeekMode;
+(function (SeekMode) {
+SeekMode[SeekMode["Start"] = 0] = "Start";
+SeekMode[SeekMode["Current"] = 1] = "Current";
+SeekMode[SeekMode["End"] = 2] = "End";
+})(SeekMode || (SeekMode = {}));
+function parseFileInfo(r){
+return {
+isFile: r.isFile,
+isDirectory: r.isDirectory,
+isSymlink: r.isSymlink,
+size: r.size,
+mtime: r.mtime !== null ? new Date(r.mtime) : null,
+atime: r.atime !== null ? new Date(r.atime) : null,
+birthtime: r.birthtime !== null ? new Date(r.birthtime) : null,
+readonly: r.readonly,
+fileAttributes: r.fileAttributes,
+dev: r.dev,
+ino: r.ino,
+mode: r.mode,
+nlink: r.nlink,
+uid: r.uid,
+gid: r.gid,
+rdev: r.rdev,
+blksize: r.blksize,
+blocks: r.blocks
+}
+}
+function fromBytes(buffer){
+const bytes = new Uint8ClampedArray(buffer)
+const size = bytes.byteLength
+let x = 0
+for (let i = 0; i < size; i++) {
+const byte = bytes[i]
+x *= 0x100
+x += byte
+}
+return x
+}
+class FileHandle extends Resource {
+async read(buffer){
+if (buffer.byteLength === 0) {
+return 0
+}
+const data = await invoke('plugin:fs|read', {
+rid: this.rid,
+len: buffer.byteLength
+})
+const nread = fromBytes(data.slice(-8))
+const bytes = data instanceof ArrayBuffer ? new Uint8Array(data) : data
+buffer.set(bytes.slice(0, bytes.length - 8))
+return nread === 0 ? null : nread
+}
+async seek(offset, whence){
+return await invoke('plugin:fs|seek', {
+rid: this.rid,
+offset,
+whence
+})
+}
+async stat(){
+const res = await invoke('plugin:fs|fstat', {
+rid: this.rid
+})
+return parseFileInfo(res)
+}
+async hash(options){
+return await runHash(
+'plugin:fs|fhash',
+{ rid: this.rid, algorithm: options?.algorithm },
+options?.onProgress,
+options?.signal
+)
+}
+async truncate(len){
+await invoke('plugin:fs|ftruncate', {
+rid: this.rid,
+len
+})
+}
+async lock(options){
+await invoke('plugin:fs|lock', {
+rid: this.rid,
+options
+})
+}
+async tryLock(mode){
+return await invoke('plugin:fs|try_lock', {
+rid: this.rid,
+mode
+})
+}
+async unlock(){
+await invoke('plugin:fs|unlock', { rid: this.rid })
+}
+async write(data){
+return await invoke('plugin:fs|write', {
+rid: this.rid,
+data
+})
+}
+}
+async function create(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+const rid = await invoke('plugin:fs|create', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+return new FileHandle(rid)
+}
+class TempPath extends Resource {
+constructor(rid, path) {
+super(rid)
+this.path = path
+}
+}
+async function tempFile(options){
+const { rid, path } = await invoke(
+'plugin:fs|create_temp_file',
+{ options }
+)
+return new TempPath(rid, path)
+}
+async function tempDir(options){
+const { rid, path } = await invoke(
+'plugin:fs|create_temp_dir',
+{ options }
+)
+return new TempPath(rid, path)
+}
+async function open(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+const rid = await invoke('plugin:fs|open', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+return new FileHandle(rid)
+}
+async function copyFile(
+fromPath,
+toPath,
+options
+){
+if (
+(fromPath instanceof URL && fromPath.protocol !== 'file:') ||
+(toPath instanceof URL && toPath.protocol !== 'file:')
+) {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|copy_file', {
+fromPath: fromPath instanceof URL ? fromPath.toString() : fromPath,
+toPath: toPath instanceof URL ? toPath.toString() : toPath,
+options
+})
+}
+async function copyDir(
+fromPath,
+toPath,
+options,
+onProgress
+){
+if (
+(fromPath instanceof URL && fromPath.protocol !== 'file:') ||
+(toPath instanceof URL && toPath.protocol !== 'file:')
+) {
+throw new TypeError('Must be a file URL.')
+}
+const channel = new Channel()
+if (onProgress) {
+channel.onmessage = onProgress
+}
+await invoke('plugin:fs|copy_dir', {
+fromPath: fromPath instanceof URL ? fromPath.toString() : fromPath,
+toPath: toPath instanceof URL ? toPath.toString() : toPath,
+options,
+onProgress: channel
+})
+}
+async function extractArchive(
+path,
+toPath,
+options,
+onProgress
+){
+if (
+(path instanceof URL && path.protocol !== 'file:') ||
+(toPath instanceof URL && toPath.protocol !== 'file:')
+) {
+throw new TypeError('Must be a file URL.')
+}
+const channel = new Channel()
+if (onProgress) {
+channel.onmessage = onProgress
+}
+await invoke('plugin:fs|extract_archive', {
+path: path instanceof URL ? path.toString() : path,
+toPath: toPath instanceof URL ? toPath.toString() : toPath,
+options,
+onProgress: channel
+})
+}
+async function createArchive(
+paths,
+toPath,
+options,
+onProgress
+){
+const archivedPaths = Array.isArray(paths) ? paths : [paths]
+for (const path of [...archivedPaths, toPath]) {
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+}
+const channel = new Channel()
+if (onProgress) {
+channel.onmessage = onProgress
+}
+await invoke('plugin:fs|create_archive', {
+paths: archivedPaths.map((p) => (p instanceof URL ? p.toString() : p)),
+toPath: toPath instanceof URL ? toPath.toString() : toPath,
+options,
+onProgress: channel
+})
+}
+async function mkdir(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|mkdir', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+}
+async function readDir(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+return await invoke('plugin:fs|read_dir', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+}
+async function walk(
+path,
+onEntries,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+const { onError, signal, ...walkOptions } = options ?? {}
+if (signal?.aborted) {
+return
+}
+let finished
+const done = new Promise((resolve) => {
+finished = resolve
+})
+const onEvent = new Channel()
+onEvent.onmessage = (event) => {
+switch (event.event) {
+case 'Entries':
+onEntries(event.data)
+break
+case 'Error':
+onError?.(event.data)
+break
+case 'Finished':
+finished()
+break
+}
+}
+const rid = await invoke('plugin:fs|walk', {
+path: path instanceof URL ? path.toString() : path,
+options: walkOptions,
+onEvent
+})
+const cancel = ()=> {
+void invoke('plugin:fs|cancel_walk', { rid })
+}
+if (signal?.aborted) {
+cancel()
+} else {
+signal?.addEventListener('abort', cancel, { once: true })
+}
+await done
+signal?.removeEventListener('abort', cancel)
+}
+async function readFile(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+const arr = await invoke('plugin:fs|read_file', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+return arr instanceof ArrayBuffer ? new Uint8Array(arr) : Uint8Array.from(arr)
+}
+async function runHash(
+cmd,
+args,
+onProgress,
+signal
+){
+if (signal?.aborted) {
+throw new Error('hashing cancelled')
+}
+let resolveHash
+let rejectHash
+const result = new Promise((resolve, reject) => {
+resolveHash = resolve
+rejectHash = reject
+})
+const onEvent = new Channel()
+onEvent.onmessage = (event) => {
+switch (event.event) {
+case 'Progress':
+onProgress?.(event.data)
+break
+case 'Finished':
+resolveHash(event.data.hash)
+break
+case 'Cancelled':
+rejectHash(new Error('hashing cancelled'))
+break
+case 'Error':
+rejectHash(new Error(event.data.message))
+break
+}
+}
+const rid = await invoke(cmd, { ...args, onEvent })
+const cancel = ()=> {
+void invoke('plugin:fs|cancel_hash', { rid })
+}
+if (signal?.aborted) {
+cancel()
+} else {
+signal?.addEventListener('abort', cancel, { once: true })
+}
+try {
+return await result
+} finally {
+signal?.removeEventListener('abort', cancel)
+}
+}
+async function hash(path, options){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+const { onProgress, signal, ...hashOptions } = options ?? {}
+return await runHash(
+'plugin:fs|hash',
+{
+path: path instanceof URL ? path.toString() : path,
+options: hashOptions
+},
+onProgress,
+signal
+)
+}
+async function readTextFile(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+return await invoke('plugin:fs|read_text_file', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+}
+async function readTextFileLines(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+const pathStr = path instanceof URL ? path.toString() : path
+return await Promise.resolve({
+path: pathStr,
+rid: null,
+async next(){
+if (this.rid === null) {
+this.rid = await invoke('plugin:fs|read_text_file_lines', {
+path: pathStr,
+options
+})
+}
+const [line, done] = await invoke(
+'plugin:fs|read_text_file_lines_next',
+{ rid: this.rid }
+)
+if (done) this.rid = null
+return {
+value: done ? '' : line,
+done
+}
+},
+[Symbol.asyncIterator](){
+return this
+}
+})
+}
+async function follow(
+path,
+onLines,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+const { onTruncated, onRotated, onError, ...followOptions } = options ?? {}
+const onEvent = new Channel()
+onEvent.onmessage = (event) => {
+switch (event.event) {
+case 'Lines':
+onLines(event.data)
+break
+case 'Truncated':
+onTruncated?.()
+break
+case 'Rotated':
+onRotated?.()
+break
+case 'Error':
+onError?.(event.data.message)
+break
+}
+}
+const rid = await invoke('plugin:fs|follow', {
+path: path instanceof URL ? path.toString() : path,
+options: followOptions,
+onEvent
+})
+return new Resource(rid)
+}
+async function remove(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|remove', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+}
+async function trash(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|trash', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+}
+async function listTrash(){
+const items = await invoke('plugin:fs|list_trash')
+return items.map((item) => ({
+...item,
+deletedAt: item.deletedAt !== null ? new Date(item.deletedAt) : null
+}))
+}
+async function restoreTrash(item){
+await invoke('plugin:fs|restore_trash', {
+id: typeof item === 'string' ? item : item.id
+})
+}
+async function rename(
+oldPath,
+newPath,
+options
+){
+if (
+(oldPath instanceof URL && oldPath.protocol !== 'file:') ||
+(newPath instanceof URL && newPath.protocol !== 'file:')
+) {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|rename', {
+oldPath: oldPath instanceof URL ? oldPath.toString() : oldPath,
+newPath: newPath instanceof URL ? newPath.toString() : newPath,
+options
+})
+}
+async function stat(
+path,
+options
+){
+const res = await invoke('plugin:fs|stat', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+return parseFileInfo(res)
+}
+async function lstat(
+path,
+options
+){
+const res = await invoke('plugin:fs|lstat', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+return parseFileInfo(res)
+}
+async function symlink(
+target,
+link,
+options
+){
+if (
+(target instanceof URL && target.protocol !== 'file:') ||
+(link instanceof URL && link.protocol !== 'file:')
+) {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|symlink', {
+target: target instanceof URL ? target.toString() : target,
+link: link instanceof URL ? link.toString() : link,
+options
+})
+}
+async function link(
+target,
+link,
+options
+){
+if (
+(target instanceof URL && target.protocol !== 'file:') ||
+(link instanceof URL && link.protocol !== 'file:')
+) {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|link', {
+target: target instanceof URL ? target.toString() : target,
+link: link instanceof URL ? link.toString() : link,
+options
+})
+}
+async function readLink(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+return await invoke('plugin:fs|read_link', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+}
+async function chmod(
+path,
+mode,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|chmod', {
+path: path instanceof URL ? path.toString() : path,
+mode,
+options
+})
+}
+async function utime(
+path,
+atime,
+mtime,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|utime', {
+path: path instanceof URL ? path.toString() : path,
+atime: Math.trunc(atime instanceof Date ? atime.getTime() : atime),
+mtime: Math.trunc(mtime instanceof Date ? mtime.getTime() : mtime),
+options
+})
+}
+async function volumeInfo(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+return await invoke('plugin:fs|volume_info', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+}
+async function truncate(
+path,
+len,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|truncate', {
+path: path instanceof URL ? path.toString() : path,
+len,
+options
+})
+}
+async function writeFile(
+path,
+data,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+await invoke('plugin:fs|write_file', data, {
+headers: {
+path: encodeURIComponent(path instanceof URL ? path.toString() : path),
+options: JSON.stringify(options)
+}
+})
+}
+async function writeTextFile(
+path,
+data,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+const encoder = new TextEncoder()
+await invoke('plugin:fs|write_text_file', encoder.encode(data), {
+headers: {
+path: encodeURIComponent(path instanceof URL ? path.toString() : path),
+options: JSON.stringify(options)
+}
+})
+}
+async function exists(
+path,
+options
+){
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+return await invoke('plugin:fs|exists', {
+path: path instanceof URL ? path.toString() : path,
+options
+})
+}
+function watchChannel(
+cb,
+onError
+){
+const channel = new Channel()
+channel.onmessage = (message) => {
+if ('error' in message) {
+onError?.(message)
+} else {
+cb(message)
+}
+}
+return channel
+}
+async function unwatch(rid){
+await invoke('plugin:fs|unwatch', { rid })
+}
+async function watch(
+paths,
+cb,
+options
+){
+const { onError, ...watchOptions } = options ?? {}
+const opts = {
+recursive: false,
+delayMs: 2000,
+...watchOptions
+}
+const watchPaths = Array.isArray(paths) ? paths : [paths]
+for (const path of watchPaths) {
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+}
+const onEvent = watchChannel(cb, onError)
+const rid= await invoke('plugin:fs|watch', {
+paths: watchPaths.map((p) => (p instanceof URL ? p.toString() : p)),
+options: opts,
+onEvent
+})
+return () => {
+void unwatch(rid)
+}
+}
+async function watchImmediate(
+paths,
+cb,
+options
+){
+const { onError, ...watchOptions } = options ?? {}
+const opts = {
+recursive: false,
+...watchOptions,
+delayMs: null
+}
+const watchPaths = Array.isArray(paths) ? paths : [paths]
+for (const path of watchPaths) {
+if (path instanceof URL && path.protocol !== 'file:') {
+throw new TypeError('Must be a file URL.')
+}
+}
+const onEvent = watchChannel(cb, onError)
+const rid= await invoke('plugin:fs|watch', {
+paths: watchPaths.map((p) => (p instanceof URL ? p.toString() : p)),
+options: opts,
+onEvent
+})
+return () => {
+void unwatch(rid)
+}
+}
+exports.BaseDirectory = BaseDirectory;
+exports.FileHandle = FileHandle;
+exports.TempPath = TempPath;
+exports.create = create;
+exports.tempFile = tempFile;
+exports.tempDir = tempDir;
+exports.open = open;
+exports.copyFile = copyFile;
+exports.copyDir = copyDir;
+exports.extractArchive = extractArchive;
+exports.createArchive = createArchive;
+exports.mkdir = mkdir;
+exports.readDir = readDir;
+exports.walk = walk;
+exports.readFile = readFile;
+exports.hash = hash;
+exports.readTextFile = readTextFile;
+exports.readTextFileLines = readTextFileLines;
+exports.follow = follow;
+exports.remove = remove;
+exports.rename = rename;
+exports.SeekMode = SeekMode;
+exports.stat = stat;
+exports.lstat = lstat;
+exports.symlink = symlink;
+exports.link = link;
+exports.readLink = readLink;
+exports.chmod = chmod;
+exports.utime = utime;
+exports.volumeInfo = volumeInfo;
+exports.trash = trash;
+exports.listTrash = listTrash;
+exports.restoreTrash = restoreTrash;
+exports.truncate = truncate;
+exports.writeFile = writeFile;
+exports.writeTextFile = writeTextFile;
+exports.exists = exists;
+exports.watch = watch;
+exports.watchImmediate = watchImmediate;
+return exports;
+})({});
+Object.defineProperty(window.__TAURI__, 'fs', { value: __TAURI_PLUGIN_FS__ })
+}
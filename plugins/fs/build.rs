@@ -66,10 +66,14 @@ const COMMANDS: &[&str] = &[
     "mkdir",
     "create",
     "copy_file",
+    "copy_dir",
     "remove",
     "rename",
     "truncate",
     "ftruncate",
+    "lock",
+    "try_lock",
+    "unlock",
     "write",
     "write_file",
     "write_text_file",
@@ -78,13 +82,32 @@ const COMMANDS: &[&str] = &[
     "read",
     "open",
     "read_text_file",
+    "hash",
+    "fhash",
+    "cancel_hash",
     "read_text_file_lines",
     "read_text_file_lines_next",
     "seek",
     "stat",
     "lstat",
     "fstat",
+    "symlink",
+    "link",
+    "read_link",
+    "chmod",
+    "utime",
+    "volume_info",
     "exists",
+    "walk",
+    "cancel_walk",
+    "follow",
+    "create_temp_file",
+    "create_temp_dir",
+    "extract_archive",
+    "create_archive",
+    "trash",
+    "list_trash",
+    "restore_trash",
     "watch",
     "unwatch",
 ];
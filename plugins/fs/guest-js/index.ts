@@ -262,6 +262,25 @@ function fromBytes(buffer: FixedSizeArray<number, 8>): number {
   return x
 }
 
+/**
+ * Advisory lock kinds:
+ * - `exclusive`: only one handle can hold the lock.
+ * - `shared`: any number of handles can hold the lock as long as no one holds it exclusively.
+ *
+ * @since 2.1.0
+ */
+type LockMode = 'exclusive' | 'shared'
+
+/**
+ * @since 2.1.0
+ */
+interface LockOptions {
+  /** Defaults to `exclusive`. */
+  mode?: LockMode
+  /** Rejects after this many milliseconds instead of waiting for the lock indefinitely. */
+  timeoutMs?: number
+}
+
 /**
  *  The Tauri abstraction for reading and writing files.
  *
@@ -384,6 +403,29 @@ class FileHandle extends Resource {
     return parseFileInfo(res)
   }
 
+  /**
+   * Computes the hex encoded digest of the whole file, regardless of the current position.
+   * Other operations on this file wait for the hashing to finish.
+   *
+   * @example
+   * ```typescript
+   * import { open, BaseDirectory } from '@tauri-apps/plugin-fs';
+   * const file = await open("backup.zip", { read: true, baseDir: BaseDirectory.AppData });
+   * const digest = await file.hash({ algorithm: 'blake3' });
+   * await file.close();
+   * ```
+   *
+   * @since 2.1.0
+   */
+  async hash(options?: Omit<HashOptions, 'baseDir'>): Promise<string> {
+    return await runHash(
+      'plugin:fs|fhash',
+      { rid: this.rid, algorithm: options?.algorithm },
+      options?.onProgress,
+      options?.signal
+    )
+  }
+
   /**
    * Truncates or extends this file, to reach the specified `len`.
    * If `len` is not specified then the entire file contents are truncated.
@@ -415,6 +457,74 @@ class FileHandle extends Resource {
     })
   }
 
+  /**
+   * Waits for an advisory lock on this file, `exclusive` by default.
+   * Rejects if `timeoutMs` is set and the lock could not be acquired in time.
+   *
+   * The lock only guards against other handles locking the same file, it does not prevent reads or writes.
+   * It is released by {@linkcode FileHandle.unlock} or when the file is closed.
+   *
+   * @example
+   * ```typescript
+   * import { open, BaseDirectory } from '@tauri-apps/plugin-fs';
+   * const file = await open("db.json", { read: true, write: true, baseDir: BaseDirectory.AppLocalData });
+   * await file.lock({ timeoutMs: 5000 });
+   * await file.write(new TextEncoder().encode("{}"));
+   * await file.unlock();
+   * await file.close();
+   * ```
+   *
+   * @since 2.1.0
+   */
+  async lock(options?: LockOptions): Promise<void> {
+    await invoke('plugin:fs|lock', {
+      rid: this.rid,
+      options
+    })
+  }
+
+  /**
+   * Acquires an advisory lock on this file without waiting, `exclusive` by default.
+   * Resolves to `false` if the lock is held by another handle.
+   *
+   * @example
+   * ```typescript
+   * import { open, BaseDirectory } from '@tauri-apps/plugin-fs';
+   * const file = await open("db.json", { read: true, baseDir: BaseDirectory.AppLocalData });
+   * if (await file.tryLock('shared')) {
+   *   // read the file
+   *   await file.unlock();
+   * }
+   * await file.close();
+   * ```
+   *
+   * @since 2.1.0
+   */
+  async tryLock(mode?: LockMode): Promise<boolean> {
+    return await invoke('plugin:fs|try_lock', {
+      rid: this.rid,
+      mode
+    })
+  }
+
+  /**
+   * Releases the advisory lock held on this file.
+   *
+   * @example
+   * ```typescript
+   * import { open, BaseDirectory } from '@tauri-apps/plugin-fs';
+   * const file = await open("db.json", { read: true, write: true, baseDir: BaseDirectory.AppLocalData });
+   * await file.lock();
+   * await file.unlock();
+   * await file.close();
+   * ```
+   *
+   * @since 2.1.0
+   */
+  async unlock(): Promise<void> {
+    await invoke('plugin:fs|unlock', { rid: this.rid })
+  }
+
   /**
    * Writes `p.byteLength` bytes from `p` to the underlying data stream. It
    * resolves to the number of bytes written from `p` (`0` <= `n` <=
@@ -481,6 +591,79 @@ async function create(
   return new FileHandle(rid)
 }
 
+/**
+ * @since 2.1.0
+ */
+interface TempOptions {
+  /** Start of the name, before its random part. */
+  prefix?: string
+  /** End of the name, after its random part, e.g. an extension. */
+  suffix?: string
+}
+
+/**
+ * A temporary file or directory created by {@linkcode tempFile} or {@linkcode tempDir}.
+ * It is removed when {@linkcode TempPath.close} is called or when the app exits.
+ *
+ * @since 2.1.0
+ */
+class TempPath extends Resource {
+  /** Absolute path of the temporary file or directory, accessible to the scope until it is removed. */
+  readonly path: string
+
+  constructor(rid: number, path: string) {
+    super(rid)
+    this.path = path
+  }
+}
+
+/**
+ * Creates an empty temporary file in a directory of the app inside the temporary directory of the system.
+ * Its path is added to the scope and it is removed when the returned {@linkcode TempPath} is closed or when the app exits.
+ *
+ * @example
+ * ```typescript
+ * import { tempFile, writeTextFile } from '@tauri-apps/plugin-fs';
+ * const temp = await tempFile({ prefix: 'report-', suffix: '.csv' });
+ * await writeTextFile(temp.path, 'name;value');
+ * // removes the file
+ * await temp.close();
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function tempFile(options?: TempOptions): Promise<TempPath> {
+  const { rid, path } = await invoke<{ rid: number; path: string }>(
+    'plugin:fs|create_temp_file',
+    { options }
+  )
+  return new TempPath(rid, path)
+}
+
+/**
+ * Creates an empty temporary directory in a directory of the app inside the temporary directory of the system.
+ * It is added to the scope with its contents and removed with them when the returned {@linkcode TempPath} is closed
+ * or when the app exits.
+ *
+ * @example
+ * ```typescript
+ * import { tempDir, writeTextFile } from '@tauri-apps/plugin-fs';
+ * const temp = await tempDir();
+ * await writeTextFile(`${temp.path}/data.json`, '{}');
+ * // removes the directory and its contents
+ * await temp.close();
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function tempDir(options?: TempOptions): Promise<TempPath> {
+  const { rid, path } = await invoke<{ rid: number; path: string }>(
+    'plugin:fs|create_temp_dir',
+    { options }
+  )
+  return new TempPath(rid, path)
+}
+
 /**
  * @since 2.0.0
  */
@@ -605,6 +788,226 @@ async function copyFile(
   })
 }
 
+/**
+ * @since 2.1.0
+ */
+interface CopyDirOptions {
+  /** Base directory for `fromPath`. */
+  fromPathBaseDir?: BaseDirectory
+  /** Base directory for `toPath`. */
+  toPathBaseDir?: BaseDirectory
+  /**
+   * What to do when a file already exists at the destination:
+   * fail the copy (`'error'`, the default), replace it (`'overwrite'`) or keep it (`'skip'`).
+   */
+  onConflict?: 'error' | 'overwrite' | 'skip'
+  /**
+   * How symbolic links are handled: create the same links at the destination (`'preserve'`, the default),
+   * copy the files they point to (`'follow'`), or ignore them (`'skip'`).
+   * The paths the links point to, from their new location when preserved, must be allowed by the scope.
+   */
+  symlinks?: 'preserve' | 'follow' | 'skip'
+}
+
+/**
+ * Progress of {@linkcode copyDir}.
+ *
+ * @since 2.1.0
+ */
+type CopyDirEvent =
+  | { event: 'Started'; data: { totalFiles: number; totalBytes: number } }
+  | {
+      event: 'Progress'
+      data: {
+        /** Path of the copied file, relative to the copied directory. */
+        path: string
+        copiedFiles: number
+        copiedBytes: number
+        /** Whether the file was skipped because it already exists at the destination. */
+        skipped: boolean
+      }
+    }
+  | { event: 'Finished' }
+
+/**
+ * Recursively copies a directory to another specified path, creating the destination directory if needed.
+ * Every copied file and directory must be allowed by the scope.
+ * @example
+ * ```typescript
+ * import { copyDir, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await copyDir('project', 'project-backup', { fromPathBaseDir: BaseDirectory.AppData, toPathBaseDir: BaseDirectory.AppData, onConflict: 'overwrite' }, (event) => {
+ *   if (event.event === 'Progress') {
+ *     console.log(`copied ${event.data.path}`);
+ *   }
+ * });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function copyDir(
+  fromPath: string | URL,
+  toPath: string | URL,
+  options?: CopyDirOptions,
+  onProgress?: (event: CopyDirEvent) => void
+): Promise<void> {
+  if (
+    (fromPath instanceof URL && fromPath.protocol !== 'file:') ||
+    (toPath instanceof URL && toPath.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const channel = new Channel<CopyDirEvent>()
+  if (onProgress) {
+    channel.onmessage = onProgress
+  }
+
+  await invoke('plugin:fs|copy_dir', {
+    fromPath: fromPath instanceof URL ? fromPath.toString() : fromPath,
+    toPath: toPath instanceof URL ? toPath.toString() : toPath,
+    options,
+    onProgress: channel
+  })
+}
+
+/**
+ * @since 2.1.0
+ */
+type ArchiveFormat = 'zip' | 'tar' | 'tar.gz'
+
+/**
+ * @since 2.1.0
+ */
+interface ExtractArchiveOptions {
+  /** Base directory for `path`. */
+  pathBaseDir?: BaseDirectory
+  /** Base directory for `toPath`. */
+  toPathBaseDir?: BaseDirectory
+  /** Format of the archive, guessed from its extension by default. */
+  format?: ArchiveFormat
+  /**
+   * What to do when an extracted file already exists:
+   * fail the extraction (`'error'`, the default), replace it (`'overwrite'`) or keep it (`'skip'`).
+   */
+  onConflict?: 'error' | 'overwrite' | 'skip'
+}
+
+/**
+ * @since 2.1.0
+ */
+interface CreateArchiveOptions {
+  /** Base directory for `paths`. */
+  pathsBaseDir?: BaseDirectory
+  /** Base directory for `toPath`. */
+  toPathBaseDir?: BaseDirectory
+  /** Format of the archive, guessed from its extension by default. */
+  format?: ArchiveFormat
+}
+
+/**
+ * Progress of {@linkcode extractArchive} and {@linkcode createArchive}.
+ *
+ * @since 2.1.0
+ */
+type ArchiveEvent =
+  | {
+      event: 'Started'
+      /** The totals are `null` when extracting tar archives. */
+      data: { totalEntries: number | null; totalBytes: number | null }
+    }
+  | {
+      event: 'Progress'
+      data: {
+        /** Path of the entry in the archive. */
+        path: string
+        processedEntries: number
+        processedBytes: number
+        /** Whether the entry was skipped because it already exists. */
+        skipped: boolean
+      }
+    }
+  | { event: 'Finished' }
+
+/**
+ * Extracts a zip, tar or tar.gz archive into a directory, creating it if needed.
+ * Every extracted entry must be allowed by the scope, and entries or symbolic links that would
+ * escape the destination directory make the extraction fail.
+ *
+ * Requires the `archive` feature of the Rust plugin.
+ * @example
+ * ```typescript
+ * import { extractArchive, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await extractArchive('bundle.zip', 'projects/imported', { pathBaseDir: BaseDirectory.Download, toPathBaseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function extractArchive(
+  path: string | URL,
+  toPath: string | URL,
+  options?: ExtractArchiveOptions,
+  onProgress?: (event: ArchiveEvent) => void
+): Promise<void> {
+  if (
+    (path instanceof URL && path.protocol !== 'file:') ||
+    (toPath instanceof URL && toPath.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const channel = new Channel<ArchiveEvent>()
+  if (onProgress) {
+    channel.onmessage = onProgress
+  }
+
+  await invoke('plugin:fs|extract_archive', {
+    path: path instanceof URL ? path.toString() : path,
+    toPath: toPath instanceof URL ? toPath.toString() : toPath,
+    options,
+    onProgress: channel
+  })
+}
+
+/**
+ * Creates a zip, tar or tar.gz archive of files and directories.
+ * Each path is stored at the root of the archive under its name and directories are added recursively.
+ * Every archived entry must be allowed by the scope.
+ *
+ * Requires the `archive` feature of the Rust plugin.
+ * @example
+ * ```typescript
+ * import { createArchive, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await createArchive(['projects/demo'], 'demo.tar.gz', { pathsBaseDir: BaseDirectory.AppData, toPathBaseDir: BaseDirectory.Download });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function createArchive(
+  paths: string | URL | Array<string | URL>,
+  toPath: string | URL,
+  options?: CreateArchiveOptions,
+  onProgress?: (event: ArchiveEvent) => void
+): Promise<void> {
+  const archivedPaths = Array.isArray(paths) ? paths : [paths]
+  for (const path of [...archivedPaths, toPath]) {
+    if (path instanceof URL && path.protocol !== 'file:') {
+      throw new TypeError('Must be a file URL.')
+    }
+  }
+
+  const channel = new Channel<ArchiveEvent>()
+  if (onProgress) {
+    channel.onmessage = onProgress
+  }
+
+  await invoke('plugin:fs|create_archive', {
+    paths: archivedPaths.map((p) => (p instanceof URL ? p.toString() : p)),
+    toPath: toPath instanceof URL ? toPath.toString() : toPath,
+    options,
+    onProgress: channel
+  })
+}
+
 /**
  * @since 2.0.0
  */
@@ -706,59 +1109,319 @@ async function readDir(
 }
 
 /**
- * @since 2.0.0
+ * @since 2.1.0
  */
-interface ReadFileOptions {
+interface WalkOptions {
   /** Base directory for `path` */
   baseDir?: BaseDirectory
+  /**
+   * Only entries whose path, relative to the walked directory, matches one of these globs are reported.
+   * `*` does not match `/` and directories that do not match are still traversed, e.g. `['*.json', 'src/**']`.
+   */
+  include?: string[]
+  /** Entries whose path, relative to the walked directory, matches one of these globs are neither reported nor traversed. */
+  exclude?: string[]
+  /** Maximum depth of the reported entries, `1` only reports the entries of the walked directory. */
+  maxDepth?: number
+  /** Whether hidden files and directories are walked. Defaults to `false`. */
+  hidden?: boolean
+  /** Whether the rules of `.gitignore` files are respected. Defaults to `false`. */
+  gitignore?: boolean
+  /** Whether symbolic links to directories are traversed. Defaults to `false`. */
+  followSymlinks?: boolean
+  /** Maximum number of entries given to each `onEntries` call. Defaults to `100`. */
+  batchSize?: number
+  /** Called when an entry can't be read, the walk goes on. */
+  onError?: (error: { path: string | null; message: string }) => void
+  /** Cancels the walk. */
+  signal?: AbortSignal
 }
 
 /**
- * Reads and resolves to the entire contents of a file as an array of bytes.
- * TextDecoder can be used to transform the bytes to string if required.
+ * An entry found by {@linkcode walk}.
+ *
+ * @since 2.1.0
+ */
+interface WalkEntry extends DirEntry {
+  /** Path of the entry relative to the walked directory. */
+  path: string
+  /** Depth of the entry, `1` for the entries of the walked directory. */
+  depth: number
+}
+
+type WalkEvent =
+  | { event: 'Entries'; data: WalkEntry[] }
+  | { event: 'Error'; data: { path: string | null; message: string } }
+  | { event: 'Finished'; data: { cancelled: boolean } }
+
+/**
+ * Recursively walks the directory given by path, reporting its entries in batches as they are found.
+ * Entries that are not allowed by the scope are left out.
+ *
+ * Resolves once the whole directory has been walked or the walk was cancelled with `options.signal`.
  * @example
  * ```typescript
- * import { readFile, BaseDirectory } from '@tauri-apps/plugin-fs';
- * const contents = await readFile('avatar.png', { baseDir: BaseDirectory.Resource });
+ * import { walk, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const controller = new AbortController();
+ * await walk('project', (entries) => {
+ *   for (const entry of entries) {
+ *     console.log(entry.path);
+ *   }
+ * }, { baseDir: BaseDirectory.AppData, include: ['src/**'], gitignore: true, signal: controller.signal });
  * ```
  *
- * @since 2.0.0
+ * @since 2.1.0
  */
-async function readFile(
+async function walk(
   path: string | URL,
-  options?: ReadFileOptions
-): Promise<Uint8Array> {
+  onEntries: (entries: WalkEntry[]) => void,
+  options?: WalkOptions
+): Promise<void> {
   if (path instanceof URL && path.protocol !== 'file:') {
     throw new TypeError('Must be a file URL.')
   }
 
-  const arr = await invoke<ArrayBuffer | number[]>('plugin:fs|read_file', {
+  const { onError, signal, ...walkOptions } = options ?? {}
+  if (signal?.aborted) {
+    return
+  }
+
+  let finished: () => void
+  const done = new Promise<void>((resolve) => {
+    finished = resolve
+  })
+
+  const onEvent = new Channel<WalkEvent>()
+  onEvent.onmessage = (event) => {
+    switch (event.event) {
+      case 'Entries':
+        onEntries(event.data)
+        break
+      case 'Error':
+        onError?.(event.data)
+        break
+      case 'Finished':
+        finished()
+        break
+    }
+  }
+
+  const rid = await invoke<number>('plugin:fs|walk', {
     path: path instanceof URL ? path.toString() : path,
-    options
+    options: walkOptions,
+    onEvent
   })
 
-  return arr instanceof ArrayBuffer ? new Uint8Array(arr) : Uint8Array.from(arr)
+  const cancel = (): void => {
+    void invoke('plugin:fs|cancel_walk', { rid })
+  }
+  if (signal?.aborted) {
+    cancel()
+  } else {
+    signal?.addEventListener('abort', cancel, { once: true })
+  }
+
+  await done
+  signal?.removeEventListener('abort', cancel)
 }
 
 /**
- * Reads and returns the entire contents of a file as UTF-8 string.
+ * @since 2.0.0
+ */
+interface ReadFileOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Reads and resolves to the entire contents of a file as an array of bytes.
+ * TextDecoder can be used to transform the bytes to string if required.
  * @example
  * ```typescript
- * import { readTextFile, BaseDirectory } from '@tauri-apps/plugin-fs';
- * const contents = await readTextFile('app.conf', { baseDir: BaseDirectory.AppConfig });
+ * import { readFile, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const contents = await readFile('avatar.png', { baseDir: BaseDirectory.Resource });
  * ```
  *
  * @since 2.0.0
  */
-async function readTextFile(
+async function readFile(
   path: string | URL,
   options?: ReadFileOptions
-): Promise<string> {
+): Promise<Uint8Array> {
   if (path instanceof URL && path.protocol !== 'file:') {
     throw new TypeError('Must be a file URL.')
   }
 
-  return await invoke<string>('plugin:fs|read_text_file', {
+  const arr = await invoke<ArrayBuffer | number[]>('plugin:fs|read_file', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+
+  return arr instanceof ArrayBuffer ? new Uint8Array(arr) : Uint8Array.from(arr)
+}
+
+/**
+ * @since 2.1.0
+ */
+type HashAlgorithm = 'sha256' | 'sha1' | 'blake3'
+
+/**
+ * @since 2.1.0
+ */
+interface HashProgress {
+  /** Number of bytes hashed so far. */
+  hashedBytes: number
+  /** Size of the file, if known. */
+  totalBytes: number | null
+}
+
+/**
+ * @since 2.1.0
+ */
+interface HashOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+  /** Defaults to `'sha256'`. */
+  algorithm?: HashAlgorithm
+  /** Called as the file is being hashed. */
+  onProgress?: (progress: HashProgress) => void
+  /** Cancels the hashing, rejecting the returned promise. */
+  signal?: AbortSignal
+}
+
+type HashEvent =
+  | { event: 'Progress'; data: HashProgress }
+  | { event: 'Finished'; data: { hash: string } }
+  | { event: 'Cancelled' }
+  | { event: 'Error'; data: { message: string } }
+
+async function runHash(
+  cmd: string,
+  args: Record<string, unknown>,
+  onProgress?: (progress: HashProgress) => void,
+  signal?: AbortSignal
+): Promise<string> {
+  if (signal?.aborted) {
+    throw new Error('hashing cancelled')
+  }
+
+  let resolveHash: (hash: string) => void
+  let rejectHash: (error: Error) => void
+  const result = new Promise<string>((resolve, reject) => {
+    resolveHash = resolve
+    rejectHash = reject
+  })
+
+  const onEvent = new Channel<HashEvent>()
+  onEvent.onmessage = (event) => {
+    switch (event.event) {
+      case 'Progress':
+        onProgress?.(event.data)
+        break
+      case 'Finished':
+        resolveHash(event.data.hash)
+        break
+      case 'Cancelled':
+        rejectHash(new Error('hashing cancelled'))
+        break
+      case 'Error':
+        rejectHash(new Error(event.data.message))
+        break
+    }
+  }
+
+  const rid = await invoke<number>(cmd, { ...args, onEvent })
+
+  const cancel = (): void => {
+    void invoke('plugin:fs|cancel_hash', { rid })
+  }
+  if (signal?.aborted) {
+    cancel()
+  } else {
+    signal?.addEventListener('abort', cancel, { once: true })
+  }
+
+  try {
+    return await result
+  } finally {
+    signal?.removeEventListener('abort', cancel)
+  }
+}
+
+/**
+ * Computes the hex encoded digest of a file, reading it in chunks so files of any size can be hashed.
+ * @example
+ * ```typescript
+ * import { hash, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const digest = await hash('installer.dmg', {
+ *   baseDir: BaseDirectory.Download,
+ *   algorithm: 'sha256',
+ *   onProgress: ({ hashedBytes, totalBytes }) => console.log(`${hashedBytes}/${totalBytes}`)
+ * });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function hash(path: string | URL, options?: HashOptions): Promise<string> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const { onProgress, signal, ...hashOptions } = options ?? {}
+
+  return await runHash(
+    'plugin:fs|hash',
+    {
+      path: path instanceof URL ? path.toString() : path,
+      options: hashOptions
+    },
+    onProgress,
+    signal
+  )
+}
+
+/**
+ * Encoding of a text file: `auto` to guess it, or any label of the
+ * [Encoding Standard](https://encoding.spec.whatwg.org/#names-and-labels),
+ * e.g. `utf-8`, `utf-16le`, `utf-16be`, `windows-1252` or `shift_jis`.
+ *
+ * @since 2.1.0
+ */
+type TextEncoding = string
+
+/**
+ * @since 2.1.0
+ */
+interface ReadTextFileOptions extends ReadFileOptions {
+  /**
+   * Encoding of the file. A byte order mark takes precedence over it,
+   * and malformed sequences are replaced by `U+FFFD`.
+   *
+   * When not set, the file must be valid UTF-8.
+   */
+  encoding?: TextEncoding
+}
+
+/**
+ * Reads and returns the entire contents of a file as UTF-8 string.
+ * @example
+ * ```typescript
+ * import { readTextFile, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const contents = await readTextFile('app.conf', { baseDir: BaseDirectory.AppConfig });
+ * const legacy = await readTextFile('export.csv', { baseDir: BaseDirectory.Download, encoding: 'windows-1252' });
+ * ```
+ *
+ * @since 2.0.0
+ */
+async function readTextFile(
+  path: string | URL,
+  options?: ReadTextFileOptions
+): Promise<string> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke<string>('plugin:fs|read_text_file', {
     path: path instanceof URL ? path.toString() : path,
     options
   })
@@ -781,7 +1444,7 @@ async function readTextFile(
  */
 async function readTextFileLines(
   path: string | URL,
-  options?: ReadFileOptions
+  options?: ReadTextFileOptions
 ): Promise<AsyncIterableIterator<string>> {
   if (path instanceof URL && path.protocol !== 'file:') {
     throw new TypeError('Must be a file URL.')
@@ -819,6 +1482,87 @@ async function readTextFileLines(
   })
 }
 
+/**
+ * @since 2.1.0
+ */
+interface FollowOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+  /** Defaults to `false`. If set to `true`, the current lines of the file are sent before the appended ones. */
+  fromStart?: boolean
+  /** Interval between two checks for appended lines, defaults to 250 milliseconds and at least 10 milliseconds. */
+  pollIntervalMs?: number
+  /** Called when the file was truncated, its lines are then read from its start again. */
+  onTruncated?: () => void
+  /** Called when the file was replaced, e.g. by a log rotation, the lines of the new file are then read from its start. */
+  onRotated?: () => void
+  /** Called when the file could not be read, it is followed again once readable. */
+  onError?: (message: string) => void
+}
+
+type FollowEvent =
+  | { event: 'Lines'; data: string[] }
+  | { event: 'Truncated' }
+  | { event: 'Rotated' }
+  | { event: 'Error'; data: { message: string } }
+
+/**
+ * Follows a growing file like `tail -F`: the lines appended to it are passed to `onLines`
+ * until the returned resource is closed. Following survives the truncation of the file
+ * and its replacement by a new file, e.g. on a log rotation.
+ *
+ * Lines are decoded as UTF-8, without their line ending.
+ *
+ * @example
+ * ```typescript
+ * import { follow, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const follower = await follow('logs/app.log', (lines) => {
+ *   console.log(lines);
+ * }, { baseDir: BaseDirectory.AppLog, onRotated: () => console.log('rotated') });
+ * // stop following
+ * await follower.close();
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function follow(
+  path: string | URL,
+  onLines: (lines: string[]) => void,
+  options?: FollowOptions
+): Promise<Resource> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const { onTruncated, onRotated, onError, ...followOptions } = options ?? {}
+
+  const onEvent = new Channel<FollowEvent>()
+  onEvent.onmessage = (event) => {
+    switch (event.event) {
+      case 'Lines':
+        onLines(event.data)
+        break
+      case 'Truncated':
+        onTruncated?.()
+        break
+      case 'Rotated':
+        onRotated?.()
+        break
+      case 'Error':
+        onError?.(event.data.message)
+        break
+    }
+  }
+
+  const rid = await invoke<number>('plugin:fs|follow', {
+    path: path instanceof URL ? path.toString() : path,
+    options: followOptions,
+    onEvent
+  })
+
+  return new Resource(rid)
+}
+
 /**
  * @since 2.0.0
  */
@@ -855,6 +1599,112 @@ async function remove(
   })
 }
 
+/**
+ * An item of the trash.
+ *
+ * @since 2.1.0
+ */
+interface TrashItem {
+  /** Location of the item in the trash, passed to {@linkcode restoreTrash}. */
+  id: string
+  name: string
+  /** Path the item was trashed from. */
+  originalPath: string
+  deletedAt: Date | null
+  isDirectory: boolean
+}
+
+interface UnparsedTrashItem {
+  id: string
+  name: string
+  originalPath: string
+  deletedAt: number | null
+  isDirectory: boolean
+}
+
+/**
+ * Moves the named file or directory to the trash instead of deleting it.
+ *
+ * Requires the `trash` feature of the Rust plugin.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux:** Follows the freedesktop.org trash specification.
+ * - **Android / iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { trash, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await trash('users/file.txt', { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function trash(
+  path: string | URL,
+  options?: StatOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|trash', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * Resolves to the items of the trash that were trashed from a path in the scope.
+ *
+ * Requires the `trash` feature of the Rust plugin.
+ *
+ * #### Platform-specific
+ *
+ * - **macOS / Windows / Android / iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { listTrash } from '@tauri-apps/plugin-fs';
+ * const items = await listTrash();
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function listTrash(): Promise<TrashItem[]> {
+  const items = await invoke<UnparsedTrashItem[]>('plugin:fs|list_trash')
+
+  return items.map((item) => ({
+    ...item,
+    deletedAt: item.deletedAt !== null ? new Date(item.deletedAt) : null
+  }))
+}
+
+/**
+ * Moves a trashed item back to its original path.
+ * Rejects if the original path is out of the scope or already exists.
+ *
+ * Requires the `trash` feature of the Rust plugin.
+ *
+ * #### Platform-specific
+ *
+ * - **macOS / Windows / Android / iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { listTrash, restoreTrash } from '@tauri-apps/plugin-fs';
+ * const [item] = await listTrash();
+ * await restoreTrash(item);
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function restoreTrash(item: TrashItem | string): Promise<void> {
+  await invoke('plugin:fs|restore_trash', {
+    id: typeof item === 'string' ? item : item.id
+  })
+}
+
 /**
  * @since 2.0.0
  */
@@ -958,6 +1808,208 @@ async function lstat(
   return parseFileInfo(res)
 }
 
+/**
+ * @since 2.1.0
+ */
+interface LinkOptions {
+  /** Base directory for `target`. */
+  targetBaseDir?: BaseDirectory
+  /** Base directory for `link`. */
+  linkBaseDir?: BaseDirectory
+}
+
+/**
+ * Creates a symbolic link at `link` pointing to `target`.
+ * Both paths must be in the scope. A relative `target` is stored as given and resolved from the directory of the link,
+ * unless `targetBaseDir` is set, in which case it is resolved from that directory and stored as an absolute path.
+ *
+ * @example
+ * ```typescript
+ * import { symlink, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await symlink('data/v2', 'data/current', { targetBaseDir: BaseDirectory.AppData, linkBaseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function symlink(
+  target: string | URL,
+  link: string | URL,
+  options?: LinkOptions
+): Promise<void> {
+  if (
+    (target instanceof URL && target.protocol !== 'file:') ||
+    (link instanceof URL && link.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|symlink', {
+    target: target instanceof URL ? target.toString() : target,
+    link: link instanceof URL ? link.toString() : link,
+    options
+  })
+}
+
+/**
+ * Creates a hard link at `link` pointing to the file at `target`.
+ * Both paths must be in the scope.
+ *
+ * @example
+ * ```typescript
+ * import { link, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await link('photo.jpg', 'backup/photo.jpg', { targetBaseDir: BaseDirectory.AppData, linkBaseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function link(
+  target: string | URL,
+  link: string | URL,
+  options?: LinkOptions
+): Promise<void> {
+  if (
+    (target instanceof URL && target.protocol !== 'file:') ||
+    (link instanceof URL && link.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|link', {
+    target: target instanceof URL ? target.toString() : target,
+    link: link instanceof URL ? link.toString() : link,
+    options
+  })
+}
+
+/**
+ * Resolves to the target of the symbolic link at `path`.
+ * Only the link must be in the scope, the target is returned as stored in the link.
+ *
+ * @example
+ * ```typescript
+ * import { readLink, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const target = await readLink('data/current', { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function readLink(
+  path: string | URL,
+  options?: StatOptions
+): Promise<string> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|read_link', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * Changes the permission bits of `path`, e.g. `0o755`.
+ *
+ * On Windows, only the owner write bit (`0o200`) is used: the file becomes read-only when it is unset.
+ *
+ * @example
+ * ```typescript
+ * import { chmod, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await chmod('bin/tool', 0o755, { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function chmod(
+  path: string | URL,
+  mode: number,
+  options?: StatOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|chmod', {
+    path: path instanceof URL ? path.toString() : path,
+    mode,
+    options
+  })
+}
+
+/**
+ * Changes the access (`atime`) and modification (`mtime`) times of `path`.
+ * Numbers are milliseconds since the Unix epoch.
+ *
+ * @example
+ * ```typescript
+ * import { utime, stat, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const { atime, mtime } = await stat('original.txt', { baseDir: BaseDirectory.AppData });
+ * await utime('copy.txt', atime ?? new Date(), mtime ?? new Date(), { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function utime(
+  path: string | URL,
+  atime: Date | number,
+  mtime: Date | number,
+  options?: StatOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|utime', {
+    path: path instanceof URL ? path.toString() : path,
+    atime: Math.trunc(atime instanceof Date ? atime.getTime() : atime),
+    mtime: Math.trunc(mtime instanceof Date ? mtime.getTime() : mtime),
+    options
+  })
+}
+
+/**
+ * Space and type of a volume.
+ *
+ * @since 2.1.0
+ */
+interface VolumeInfo {
+  totalBytes: number
+  freeBytes: number
+  /** Free bytes usable by the current user, which excludes the blocks reserved to the system. */
+  availableBytes: number
+  /** File system type, e.g. `ext4`, `apfs` or `NTFS`, `null` if it could not be determined. */
+  fsType: string | null
+  readonly: boolean
+}
+
+/**
+ * Resolves to the space and type of the volume containing `path`.
+ *
+ * @example
+ * ```typescript
+ * import { volumeInfo, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const { availableBytes } = await volumeInfo('datasets', { baseDir: BaseDirectory.AppData });
+ * if (availableBytes < datasetSize) {
+ *   throw new Error('not enough space');
+ * }
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function volumeInfo(
+  path: string | URL,
+  options?: StatOptions
+): Promise<VolumeInfo> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|volume_info', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
 /**
  * @since 2.0.0
  */
@@ -1016,6 +2068,34 @@ interface WriteFileOptions {
   mode?: number
   /** Base directory for `path` */
   baseDir?: BaseDirectory
+  /**
+   * Defaults to `false`. If set to `true`, `data` is written to a temporary file next to `path` which is then renamed over it,
+   * so a crash never leaves a partially written file behind. The permissions of an existing file are preserved.
+   *
+   * @since 2.1.0
+   */
+  atomic?: boolean
+  /**
+   * Defaults to `false`. If set to `true`, the written data is flushed to the disk before resolving.
+   *
+   * @since 2.1.0
+   */
+  sync?: boolean
+  /**
+   * Encoding of the file written by {@linkcode writeTextFile}, defaults to `utf-8`.
+   * `auto` keeps the encoding of the existing file.
+   * Rejects if the text contains characters that can't be represented in the encoding.
+   *
+   * @since 2.1.0
+   */
+  encoding?: TextEncoding
+  /**
+   * Defaults to `false`. If set to `true`, {@linkcode writeTextFile} starts the file with a byte order mark
+   * when the encoding is `utf-8`, `utf-16le` or `utf-16be`. Not written when appending.
+   *
+   * @since 2.1.0
+   */
+  bom?: boolean
 }
 
 /**
@@ -1119,8 +2199,48 @@ interface WatchOptions {
   recursive?: boolean
   /** Base directory for `path` */
   baseDir?: BaseDirectory
+  /**
+   * Globs of the paths whose events are ignored, relative to the watched paths, e.g. `['dist/**']`.
+   *
+   * @since 2.1.0
+   */
+  ignore?: string[]
+  /**
+   * Called when the watcher reports an error.
+   *
+   * @since 2.1.0
+   */
+  onError?: (error: WatchError) => void
+  /**
+   * How the changes are detected, defaults to `'native'`.
+   *
+   * @since 2.1.0
+   */
+  mode?: WatchMode
+  /**
+   * Interval in milliseconds between two scans of the polling watcher, defaults to 2 seconds.
+   *
+   * @since 2.1.0
+   */
+  pollIntervalMs?: number
+  /**
+   * Whether the polling watcher compares the file contents instead of their modification time.
+   *
+   * @since 2.1.0
+   */
+  compareContents?: boolean
 }
 
+/**
+ * How the file system changes are detected:
+ * - `'native'`: uses the notifications of the operating system.
+ * - `'poll'`: scans the watched paths periodically, which works on any file system.
+ * - `'auto'`: polls the paths on network file systems or when the native watcher can't be created.
+ *
+ * @since 2.1.0
+ */
+type WatchMode = 'native' | 'poll' | 'auto'
+
 /**
  * @since 2.0.0
  */
@@ -1138,6 +2258,14 @@ interface WatchEvent {
   attrs: unknown
 }
 
+/**
+ * @since 2.1.0
+ */
+interface WatchError {
+  error: string
+  paths: string[]
+}
+
 /**
  * @since 2.0.0
  */
@@ -1201,6 +2329,21 @@ type WatchEventKindRemove =
  */
 type UnwatchFn = () => void
 
+function watchChannel(
+  cb: (event: WatchEvent) => void,
+  onError?: (error: WatchError) => void
+): Channel<WatchEvent | WatchError> {
+  const channel = new Channel<WatchEvent | WatchError>()
+  channel.onmessage = (message) => {
+    if ('error' in message) {
+      onError?.(message)
+    } else {
+      cb(message)
+    }
+  }
+  return channel
+}
+
 async function unwatch(rid: number): Promise<void> {
   await invoke('plugin:fs|unwatch', { rid })
 }
@@ -1215,10 +2358,11 @@ async function watch(
   cb: (event: WatchEvent) => void,
   options?: DebouncedWatchOptions
 ): Promise<UnwatchFn> {
+  const { onError, ...watchOptions } = options ?? {}
   const opts = {
     recursive: false,
     delayMs: 2000,
-    ...options
+    ...watchOptions
   }
 
   const watchPaths = Array.isArray(paths) ? paths : [paths]
@@ -1229,8 +2373,7 @@ async function watch(
     }
   }
 
-  const onEvent = new Channel<WatchEvent>()
-  onEvent.onmessage = cb
+  const onEvent = watchChannel(cb, onError)
 
   const rid: number = await invoke('plugin:fs|watch', {
     paths: watchPaths.map((p) => (p instanceof URL ? p.toString() : p)),
@@ -1253,9 +2396,10 @@ async function watchImmediate(
   cb: (event: WatchEvent) => void,
   options?: WatchOptions
 ): Promise<UnwatchFn> {
+  const { onError, ...watchOptions } = options ?? {}
   const opts = {
     recursive: false,
-    ...options,
+    ...watchOptions,
     delayMs: null
   }
 
@@ -1267,8 +2411,7 @@ async function watchImmediate(
     }
   }
 
-  const onEvent = new Channel<WatchEvent>()
-  onEvent.onmessage = cb
+  const onEvent = watchChannel(cb, onError)
 
   const rid: number = await invoke('plugin:fs|watch', {
     paths: watchPaths.map((p) => (p instanceof URL ? p.toString() : p)),
@@ -1283,15 +2426,33 @@ async function watchImmediate(
 
 export type {
   CreateOptions,
+  TempOptions,
   OpenOptions,
   CopyFileOptions,
+  CopyDirOptions,
+  CopyDirEvent,
+  ArchiveFormat,
+  ExtractArchiveOptions,
+  CreateArchiveOptions,
+  ArchiveEvent,
   MkdirOptions,
   DirEntry,
   ReadDirOptions,
+  WalkOptions,
+  WalkEntry,
   ReadFileOptions,
+  ReadTextFileOptions,
+  TextEncoding,
+  FollowOptions,
+  HashAlgorithm,
+  HashOptions,
+  HashProgress,
   RemoveOptions,
   RenameOptions,
   StatOptions,
+  LinkOptions,
+  VolumeInfo,
+  TrashItem,
   TruncateOptions,
   WriteFileOptions,
   ExistsOptions,
@@ -1299,6 +2460,10 @@ export type {
   WatchOptions,
   DebouncedWatchOptions,
   WatchEvent,
+  WatchError,
+  WatchMode,
+  LockMode,
+  LockOptions,
   WatchEventKind,
   WatchEventKindAccess,
   WatchEventKindCreate,
@@ -1310,19 +2475,37 @@ export type {
 export {
   BaseDirectory,
   FileHandle,
+  TempPath,
   create,
+  tempFile,
+  tempDir,
   open,
   copyFile,
+  copyDir,
+  extractArchive,
+  createArchive,
   mkdir,
   readDir,
+  walk,
   readFile,
+  hash,
   readTextFile,
   readTextFileLines,
+  follow,
   remove,
   rename,
   SeekMode,
   stat,
   lstat,
+  symlink,
+  link,
+  readLink,
+  chmod,
+  utime,
+  volumeInfo,
+  trash,
+  listTrash,
+  restoreTrash,
   truncate,
   writeFile,
   writeTextFile,
@@ -0,0 +1,773 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    path::BaseDirectory,
+    Runtime, Webview,
+};
+
+use std::{
+    fs::File,
+    io::{Read, Seek, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    commands::{
+        collect_copy_entries, create_symlink, fs_scope, resolve_link_target, resolve_path,
+        resolve_symlinks, CommandResult, CopyConflict, CopyEntry, CopyEntryKind, SymlinkPolicy,
+    },
+    scope::Entry,
+    Error, SafeFilePath,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip,
+    #[serde(rename = "tar")]
+    Tar,
+    #[serde(rename = "tar.gz")]
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Guesses the format from the extension of `path`.
+    fn from_path(path: &Path) -> crate::Result<Self> {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if name.ends_with(".zip") {
+            Ok(Self::Zip)
+        } else if name.ends_with(".tar") {
+            Ok(Self::Tar)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Ok(Self::TarGz)
+        } else {
+            Err(Error::UnknownArchiveFormat(path.to_path_buf()))
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractArchiveOptions {
+    path_base_dir: Option<BaseDirectory>,
+    to_path_base_dir: Option<BaseDirectory>,
+    /// Guessed from the extension of the archive if not set
+    format: Option<ArchiveFormat>,
+    #[serde(default)]
+    on_conflict: CopyConflict,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateArchiveOptions {
+    paths_base_dir: Option<BaseDirectory>,
+    to_path_base_dir: Option<BaseDirectory>,
+    /// Guessed from the extension of the archive if not set
+    format: Option<ArchiveFormat>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data")]
+pub enum ArchiveEvent {
+    /// The totals are unknown when extracting tar archives
+    #[serde(rename_all = "camelCase")]
+    Started {
+        total_entries: Option<u64>,
+        total_bytes: Option<u64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Progress {
+        path: PathBuf,
+        processed_entries: u64,
+        processed_bytes: u64,
+        skipped: bool,
+    },
+    Finished,
+}
+
+enum ArchiveEntryKind {
+    Dir,
+    File,
+    Symlink(PathBuf),
+    HardLink(PathBuf),
+}
+
+/// Extracts archive entries into a directory, making sure none of them ends up outside of it.
+struct Extractor<A: Fn(&Path) -> bool, P: FnMut(ArchiveEvent)> {
+    root: PathBuf,
+    canonical_root: PathBuf,
+    on_conflict: CopyConflict,
+    is_allowed: A,
+    on_progress: P,
+    processed_entries: u64,
+    processed_bytes: u64,
+}
+
+impl<A: Fn(&Path) -> bool, P: FnMut(ArchiveEvent)> Extractor<A, P> {
+    fn new(
+        root: &Path,
+        on_conflict: CopyConflict,
+        is_allowed: A,
+        on_progress: P,
+    ) -> crate::Result<Self> {
+        std::fs::create_dir_all(root)?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            canonical_root: dunce::canonicalize(root)?,
+            on_conflict,
+            is_allowed,
+            on_progress,
+            processed_entries: 0,
+            processed_bytes: 0,
+        })
+    }
+
+    /// Resolves the destination of the entry `name`, rejecting absolute paths, parent directory
+    /// components and parent directories that are symbolic links leading out of the destination.
+    fn destination(&self, name: &Path) -> crate::Result<PathBuf> {
+        if name
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(Error::UnsafeArchiveEntry(name.to_path_buf()));
+        }
+
+        let destination = self.root.join(name);
+        if !(self.is_allowed)(&destination) {
+            return Err(Error::PathForbidden(destination));
+        }
+
+        if let Some(parent) = destination.parent() {
+            // checked before creating the parent directories, which could otherwise be created
+            // through a link extracted earlier
+            if !resolve_symlinks(parent)?.starts_with(&self.canonical_root) {
+                return Err(Error::UnsafeArchiveEntry(name.to_path_buf()));
+            }
+            std::fs::create_dir_all(parent)?;
+        }
+
+        Ok(destination)
+    }
+
+    /// Checks that a symbolic link at `destination` pointing to `target` stays in the destination directory,
+    /// following the links already extracted.
+    fn check_link_target(
+        &self,
+        name: &Path,
+        destination: &Path,
+        target: &Path,
+    ) -> crate::Result<PathBuf> {
+        if target.has_root() {
+            return Err(Error::UnsafeArchiveEntry(name.to_path_buf()));
+        }
+
+        let resolved = resolve_link_target(destination, target)?;
+        if !resolved.starts_with(&self.canonical_root) {
+            return Err(Error::UnsafeArchiveEntry(name.to_path_buf()));
+        }
+        if !(self.is_allowed)(&resolved) {
+            return Err(Error::PathForbidden(resolved));
+        }
+        Ok(resolved)
+    }
+
+    fn extract(
+        &mut self,
+        name: &Path,
+        kind: ArchiveEntryKind,
+        #[allow(unused_variables)] mode: Option<u32>,
+        contents: &mut dyn Read,
+    ) -> crate::Result<()> {
+        let destination = self.destination(name)?;
+
+        if let ArchiveEntryKind::Dir = kind {
+            if !resolve_symlinks(&destination)?.starts_with(&self.canonical_root) {
+                return Err(Error::UnsafeArchiveEntry(name.to_path_buf()));
+            }
+            std::fs::create_dir_all(&destination)?;
+            return Ok(());
+        }
+
+        let existing = std::fs::symlink_metadata(&destination).ok();
+        let skipped = existing.is_some() && self.on_conflict == CopyConflict::Skip;
+        if let Some(existing) = existing {
+            match self.on_conflict {
+                CopyConflict::Error => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::AlreadyExists,
+                        format!("{} already exists", destination.display()),
+                    )
+                    .into())
+                }
+                // never write through an existing symbolic link
+                CopyConflict::Overwrite if existing.is_dir() => {
+                    std::fs::remove_dir_all(&destination)?
+                }
+                CopyConflict::Overwrite => std::fs::remove_file(&destination)?,
+                CopyConflict::Skip => {}
+            }
+        }
+
+        let mut bytes = 0;
+        if !skipped {
+            match kind {
+                ArchiveEntryKind::Dir => unreachable!(),
+                ArchiveEntryKind::File => {
+                    let mut file = File::create(&destination)?;
+                    bytes = std::io::copy(contents, &mut file)?;
+                    #[cfg(unix)]
+                    if let Some(mode) = mode {
+                        use std::os::unix::fs::PermissionsExt;
+                        // special bits such as setuid are never restored
+                        file.set_permissions(std::fs::Permissions::from_mode(mode & 0o777))?;
+                    }
+                }
+                ArchiveEntryKind::Symlink(target) => {
+                    let resolved = self.check_link_target(name, &destination, &target)?;
+                    create_symlink(&target, &resolved, &destination)?;
+                }
+                ArchiveEntryKind::HardLink(target) => {
+                    let source = self.destination(&target)?;
+                    std::fs::hard_link(source, &destination)?;
+                }
+            }
+        }
+
+        self.processed_entries += 1;
+        self.processed_bytes += bytes;
+        (self.on_progress)(ArchiveEvent::Progress {
+            path: name.to_path_buf(),
+            processed_entries: self.processed_entries,
+            processed_bytes: self.processed_bytes,
+            skipped,
+        });
+
+        Ok(())
+    }
+}
+
+fn extract_zip<A: Fn(&Path) -> bool, P: FnMut(ArchiveEvent)>(
+    archive: impl Read + Seek,
+    mut extractor: Extractor<A, P>,
+) -> crate::Result<()> {
+    let mut archive = zip::ZipArchive::new(archive)?;
+
+    let mut total_entries = 0;
+    let mut total_bytes = 0;
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        if !entry.is_dir() {
+            total_entries += 1;
+            total_bytes += entry.size();
+        }
+    }
+    (extractor.on_progress)(ArchiveEvent::Started {
+        total_entries: Some(total_entries),
+        total_bytes: Some(total_bytes),
+    });
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let name = entry
+            .enclosed_name()
+            .ok_or_else(|| Error::UnsafeArchiveEntry(PathBuf::from(entry.name())))?;
+        let kind = if entry.is_dir() {
+            ArchiveEntryKind::Dir
+        } else if entry.is_symlink() {
+            let mut target = String::new();
+            entry.read_to_string(&mut target)?;
+            ArchiveEntryKind::Symlink(PathBuf::from(target))
+        } else {
+            ArchiveEntryKind::File
+        };
+        let mode = entry.unix_mode();
+        extractor.extract(&name, kind, mode, &mut entry)?;
+    }
+
+    (extractor.on_progress)(ArchiveEvent::Finished);
+
+    Ok(())
+}
+
+fn extract_tar<A: Fn(&Path) -> bool, P: FnMut(ArchiveEvent)>(
+    archive: impl Read,
+    mut extractor: Extractor<A, P>,
+) -> crate::Result<()> {
+    let mut archive = tar::Archive::new(archive);
+
+    (extractor.on_progress)(ArchiveEvent::Started {
+        total_entries: None,
+        total_bytes: None,
+    });
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.into_owned();
+        let link_name = entry.link_name()?.map(|link| link.into_owned());
+        let kind = match (entry.header().entry_type(), link_name) {
+            (tar::EntryType::Directory, _) => ArchiveEntryKind::Dir,
+            (tar::EntryType::Regular | tar::EntryType::Continuous, _) => ArchiveEntryKind::File,
+            (tar::EntryType::Symlink, Some(target)) => ArchiveEntryKind::Symlink(target),
+            (tar::EntryType::Link, Some(target)) => ArchiveEntryKind::HardLink(target),
+            // devices, fifos and metadata entries are never extracted
+            _ => continue,
+        };
+        let mode = entry.header().mode().ok();
+        extractor.extract(&name, kind, mode, &mut entry)?;
+    }
+
+    (extractor.on_progress)(ArchiveEvent::Finished);
+
+    Ok(())
+}
+
+fn extract_archive_inner(
+    path: &Path,
+    to: &Path,
+    format: ArchiveFormat,
+    on_conflict: CopyConflict,
+    is_allowed: impl Fn(&Path) -> bool,
+    on_progress: impl FnMut(ArchiveEvent),
+) -> crate::Result<()> {
+    let archive = std::io::BufReader::new(File::open(path)?);
+    let extractor = Extractor::new(to, on_conflict, is_allowed, on_progress)?;
+    match format {
+        ArchiveFormat::Zip => extract_zip(archive, extractor),
+        ArchiveFormat::Tar => extract_tar(archive, extractor),
+        ArchiveFormat::TarGz => extract_tar(flate2::read::GzDecoder::new(archive), extractor),
+    }
+}
+
+/// Converts a relative path to an archive entry name.
+fn entry_name(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Lists `paths` and the content of the directories among them,
+/// named after the last component of each path in the archive.
+fn collect_archive_entries(
+    paths: &[PathBuf],
+    is_allowed: &impl Fn(&Path) -> bool,
+) -> crate::Result<Vec<(PathBuf, CopyEntry)>> {
+    let mut entries = Vec::new();
+    for path in paths {
+        let name = PathBuf::from(
+            path.file_name()
+                .ok_or_else(|| Error::UnsafeArchiveEntry(path.clone()))?,
+        );
+        let metadata = std::fs::symlink_metadata(path)?;
+        if metadata.is_symlink() {
+            entries.push((
+                path.clone(),
+                CopyEntry {
+                    path: name,
+                    kind: CopyEntryKind::Symlink(std::fs::read_link(path)?),
+                },
+            ));
+        } else if metadata.is_dir() {
+            entries.push((
+                path.clone(),
+                CopyEntry {
+                    path: name.clone(),
+                    kind: CopyEntryKind::Dir,
+                },
+            ));
+            let mut dir_entries = Vec::new();
+            collect_copy_entries(
+                path,
+                Path::new(""),
+                SymlinkPolicy::Preserve,
+                is_allowed,
+                &mut vec![dunce::canonicalize(path)?],
+                &mut dir_entries,
+            )?;
+            entries.extend(dir_entries.into_iter().map(|entry| {
+                (
+                    path.join(&entry.path),
+                    CopyEntry {
+                        path: name.join(entry.path),
+                        kind: entry.kind,
+                    },
+                )
+            }));
+        } else {
+            entries.push((
+                path.clone(),
+                CopyEntry {
+                    path: name,
+                    kind: CopyEntryKind::File(metadata.len()),
+                },
+            ));
+        }
+    }
+    Ok(entries)
+}
+
+fn write_zip(
+    archive: File,
+    entries: Vec<(PathBuf, CopyEntry)>,
+    mut on_entry: impl FnMut(&CopyEntry),
+) -> crate::Result<()> {
+    let mut zip = zip::ZipWriter::new(archive);
+    for (source, entry) in &entries {
+        let name = entry_name(&entry.path);
+        let mut options = zip::write::SimpleFileOptions::default();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            options =
+                options.unix_permissions(std::fs::symlink_metadata(source)?.permissions().mode());
+        }
+        match &entry.kind {
+            CopyEntryKind::Dir => zip.add_directory(name, options)?,
+            CopyEntryKind::File(_) => {
+                zip.start_file(name, options)?;
+                std::io::copy(&mut File::open(source)?, &mut zip)?;
+            }
+            CopyEntryKind::Symlink(target) => {
+                // absolute targets are stored as they are
+                let target = if target.has_root() {
+                    target.to_string_lossy().into_owned()
+                } else {
+                    entry_name(target)
+                };
+                zip.add_symlink(name, target, options)?
+            }
+        }
+        on_entry(entry);
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+fn write_tar<W: Write>(
+    archive: W,
+    entries: Vec<(PathBuf, CopyEntry)>,
+    mut on_entry: impl FnMut(&CopyEntry),
+) -> crate::Result<W> {
+    let mut tar = tar::Builder::new(archive);
+    tar.follow_symlinks(false);
+    for (source, entry) in &entries {
+        tar.append_path_with_name(source, &entry.path)?;
+        on_entry(entry);
+    }
+    Ok(tar.into_inner()?)
+}
+
+fn create_archive_inner(
+    paths: &[PathBuf],
+    to: &Path,
+    format: ArchiveFormat,
+    is_allowed: impl Fn(&Path) -> bool,
+    mut on_progress: impl FnMut(ArchiveEvent),
+) -> crate::Result<()> {
+    let entries = collect_archive_entries(paths, &is_allowed)?;
+
+    let (total_entries, total_bytes) =
+        entries
+            .iter()
+            .fold((0, 0), |(count, bytes), (_, entry)| match entry.kind {
+                CopyEntryKind::Dir => (count, bytes),
+                CopyEntryKind::File(len) => (count + 1, bytes + len),
+                CopyEntryKind::Symlink(_) => (count + 1, bytes),
+            });
+    on_progress(ArchiveEvent::Started {
+        total_entries: Some(total_entries),
+        total_bytes: Some(total_bytes),
+    });
+
+    let mut processed_entries = 0;
+    let mut processed_bytes = 0;
+    let on_entry = |entry: &CopyEntry| {
+        let len = match entry.kind {
+            CopyEntryKind::Dir => return,
+            CopyEntryKind::File(len) => len,
+            CopyEntryKind::Symlink(_) => 0,
+        };
+        processed_entries += 1;
+        processed_bytes += len;
+        on_progress(ArchiveEvent::Progress {
+            path: entry.path.clone(),
+            processed_entries,
+            processed_bytes,
+            skipped: false,
+        });
+    };
+
+    let archive = File::create(to)?;
+    let result = match format {
+        ArchiveFormat::Zip => write_zip(archive, entries, on_entry),
+        ArchiveFormat::Tar => write_tar(std::io::BufWriter::new(archive), entries, on_entry)
+            .and_then(|mut w| {
+                w.flush()?;
+                Ok(())
+            }),
+        ArchiveFormat::TarGz => write_tar(
+            flate2::write::GzEncoder::new(archive, flate2::Compression::default()),
+            entries,
+            on_entry,
+        )
+        .and_then(|gz| {
+            gz.finish()?;
+            Ok(())
+        }),
+    };
+    // do not leave a partial archive behind
+    if result.is_err() {
+        let _ = std::fs::remove_file(to);
+    }
+    result?;
+
+    on_progress(ArchiveEvent::Finished);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn extract_archive<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    to_path: SafeFilePath,
+    options: Option<ExtractArchiveOptions>,
+    on_progress: Channel<ArchiveEvent>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.path_base_dir,
+    )?;
+    let resolved_to_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        to_path,
+        options.to_path_base_dir,
+    )?;
+    let scope = fs_scope(&webview, &global_scope, &command_scope)?;
+    let format = match options.format {
+        Some(format) => format,
+        None => ArchiveFormat::from_path(&resolved_path)?,
+    };
+
+    extract_archive_inner(
+        &resolved_path,
+        &resolved_to_path,
+        format,
+        options.on_conflict,
+        |path| scope.is_allowed(path),
+        |event| {
+            let _ = on_progress.send(event);
+        },
+    )
+    .map_err(|e| {
+        format!(
+            "failed to extract archive at path: {} to path: {} with error: {e}",
+            resolved_path.display(),
+            resolved_to_path.display()
+        )
+        .into()
+    })
+}
+
+#[tauri::command]
+pub async fn create_archive<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    paths: Vec<SafeFilePath>,
+    to_path: SafeFilePath,
+    options: Option<CreateArchiveOptions>,
+    on_progress: Channel<ArchiveEvent>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let mut resolved_paths = Vec::with_capacity(paths.len());
+    for path in paths {
+        resolved_paths.push(resolve_path(
+            &webview,
+            &global_scope,
+            &command_scope,
+            path,
+            options.paths_base_dir,
+        )?);
+    }
+    let resolved_to_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        to_path,
+        options.to_path_base_dir,
+    )?;
+    let scope = fs_scope(&webview, &global_scope, &command_scope)?;
+    let format = match options.format {
+        Some(format) => format,
+        None => ArchiveFormat::from_path(&resolved_to_path)?,
+    };
+
+    create_archive_inner(
+        &resolved_paths,
+        &resolved_to_path,
+        format,
+        |path| scope.is_allowed(path),
+        |event| {
+            let _ = on_progress.send(event);
+        },
+    )
+    .map_err(|e| {
+        format!(
+            "failed to create archive at path: {} with error: {e}",
+            resolved_to_path.display()
+        )
+        .into()
+    })
+}
+
+mod test {
+    #[test]
+    fn archive_roundtrip() {
+        use super::{create_archive_inner, extract_archive_inner, ArchiveFormat};
+        use crate::commands::CopyConflict;
+
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        let project = root.join("project");
+        std::fs::create_dir_all(project.join("assets")).unwrap();
+        std::fs::write(project.join("project.json"), "{}").unwrap();
+        std::fs::write(project.join("assets/logo.svg"), "<svg/>").unwrap();
+
+        for (format, name) in [
+            (ArchiveFormat::Zip, "project.zip"),
+            (ArchiveFormat::Tar, "project.tar"),
+            (ArchiveFormat::TarGz, "project.tar.gz"),
+        ] {
+            let archive = root.join(name);
+            create_archive_inner(
+                std::slice::from_ref(&project),
+                &archive,
+                format,
+                |_| true,
+                |_| {},
+            )
+            .unwrap();
+
+            let to = root.join(format!("{name}-extracted"));
+            extract_archive_inner(&archive, &to, format, CopyConflict::Error, |_| true, |_| {})
+                .unwrap();
+            assert_eq!(
+                std::fs::read_to_string(to.join("project/assets/logo.svg")).unwrap(),
+                "<svg/>"
+            );
+
+            // the files already exist
+            assert!(extract_archive_inner(
+                &archive,
+                &to,
+                format,
+                CopyConflict::Error,
+                |_| true,
+                |_| {}
+            )
+            .is_err());
+            extract_archive_inner(&archive, &to, format, CopyConflict::Skip, |_| true, |_| {})
+                .unwrap();
+        }
+
+        // entries out of the scope are rejected
+        assert!(extract_archive_inner(
+            &root.join("project.zip"),
+            &root.join("denied"),
+            ArchiveFormat::Zip,
+            CopyConflict::Error,
+            |path| !path.ends_with("project.json"),
+            |_| {}
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn archive_rejects_escaping_entries() {
+        use super::{extract_archive_inner, ArchiveFormat};
+        use crate::commands::CopyConflict;
+
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+
+        let extract = |entries: &[(&str, tar::EntryType, &str)]| {
+            let archive = root.join("evil.tar");
+            let mut builder = tar::Builder::new(std::fs::File::create(&archive).unwrap());
+            for (name, entry_type, contents) in entries {
+                let mut header = tar::Header::new_gnu();
+                // `set_path` refuses parent directory components
+                header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+                header.set_entry_type(*entry_type);
+                if *entry_type == tar::EntryType::Symlink {
+                    header.set_link_name(contents).unwrap();
+                    header.set_size(0);
+                    header.set_cksum();
+                    builder.append(&header, std::io::empty()).unwrap();
+                } else {
+                    header.set_size(contents.len() as u64);
+                    header.set_mode(0o644);
+                    header.set_cksum();
+                    builder.append(&header, contents.as_bytes()).unwrap();
+                }
+            }
+            builder.finish().unwrap();
+            drop(builder);
+
+            extract_archive_inner(
+                &archive,
+                &root.join("out"),
+                ArchiveFormat::Tar,
+                CopyConflict::Overwrite,
+                |_| true,
+                |_| {},
+            )
+        };
+
+        assert!(extract(&[("../evil.txt", tar::EntryType::Regular, "evil")]).is_err());
+        assert!(!root.join("evil.txt").exists());
+
+        assert!(extract(&[
+            ("link", tar::EntryType::Symlink, ".."),
+            ("link/evil.txt", tar::EntryType::Regular, "evil"),
+        ])
+        .is_err());
+        assert!(!root.join("evil.txt").exists());
+
+        // chained links resolve out of the destination
+        assert!(extract(&[
+            ("d", tar::EntryType::Symlink, "."),
+            ("d2", tar::EntryType::Symlink, "d/.."),
+            ("d2/evil.txt", tar::EntryType::Regular, "evil"),
+        ])
+        .is_err());
+        assert!(!root.join("evil.txt").exists());
+
+        // links within the destination are fine
+        extract(&[
+            ("data.txt", tar::EntryType::Regular, "data"),
+            ("link.txt", tar::EntryType::Symlink, "data.txt"),
+        ])
+        .unwrap();
+        #[cfg(unix)]
+        assert_eq!(
+            std::fs::read_to_string(root.join("out/link.txt")).unwrap(),
+            "data"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Atomic file writes: the contents are written to a temporary file next to the target
+//! which is then renamed over it, so the target is either left untouched or fully written.
+
+use std::{
+    fs::File,
+    io::{Error, ErrorKind, Result, Write},
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AtomicWriteOptions {
+    /// Keep the current contents of the target and write after them
+    pub append: bool,
+    /// Create the target if it does not exist
+    pub create: bool,
+    /// Fail if the target exists
+    pub create_new: bool,
+    /// Permissions of a new target, existing targets keep theirs
+    pub mode: Option<u32>,
+    /// Flush the file and its directory to the disk before returning
+    pub sync: bool,
+}
+
+impl Default for AtomicWriteOptions {
+    fn default() -> Self {
+        Self {
+            append: false,
+            create: true,
+            create_new: false,
+            mode: None,
+            sync: false,
+        }
+    }
+}
+
+fn temp_path(path: &Path) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "path has no file name"))?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(".{}.tmp", uuid::Uuid::new_v4()));
+    Ok(path.with_file_name(temp_name))
+}
+
+pub(crate) fn write(path: &Path, data: &[u8], options: AtomicWriteOptions) -> Result<()> {
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => Some(metadata),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => return Err(e),
+    };
+    match &metadata {
+        Some(_) if options.create_new => {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} already exists", path.display()),
+            ))
+        }
+        None if !options.create && !options.create_new => {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("{} does not exist", path.display()),
+            ))
+        }
+        _ => {}
+    }
+
+    let temp_path = temp_path(path)?;
+    let result = write_temp(&temp_path, path, data, metadata.as_ref(), options)
+        .and_then(|()| std::fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result?;
+
+    // the rename itself is only durable once the directory entry is flushed
+    #[cfg(unix)]
+    if options.sync {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            File::open(parent)?.sync_all()?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_temp(
+    temp_path: &Path,
+    path: &Path,
+    data: &[u8],
+    metadata: Option<&std::fs::Metadata>,
+    options: AtomicWriteOptions,
+) -> Result<()> {
+    let mut open_options = std::fs::OpenOptions::new();
+    open_options.write(true).create_new(true);
+    #[cfg(unix)]
+    if let Some(mode) = options.mode {
+        use std::os::unix::fs::OpenOptionsExt;
+        open_options.mode(mode);
+    }
+    let mut file = open_options.open(temp_path)?;
+
+    if options.append && metadata.is_some() {
+        std::io::copy(&mut File::open(path)?, &mut file)?;
+    }
+    file.write_all(data)?;
+
+    if let Some(metadata) = metadata {
+        file.set_permissions(metadata.permissions())?;
+    }
+    if options.sync {
+        file.sync_all()?;
+    }
+
+    Ok(())
+}
+
+mod test {
+    #[test]
+    fn atomic_write_replaces_the_target() {
+        use super::{write, AtomicWriteOptions};
+        use std::io::ErrorKind;
+
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let path = dir.join("config.json");
+
+        write(&path, b"{}", AtomicWriteOptions::default()).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"{}");
+
+        let append = AtomicWriteOptions {
+            append: true,
+            sync: true,
+            ..Default::default()
+        };
+        write(&path, b"\n", append).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"{}\n");
+
+        let create_new = AtomicWriteOptions {
+            create_new: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            write(&path, b"", create_new).unwrap_err().kind(),
+            ErrorKind::AlreadyExists
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o700)).unwrap();
+            write(&path, b"{}", AtomicWriteOptions::default()).unwrap();
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+
+        // no temporary file is left behind
+        assert_eq!(std::fs::read_dir(dir).unwrap().count(), 1);
+    }
+}
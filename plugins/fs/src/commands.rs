@@ -6,7 +6,7 @@
 use serde::{Deserialize, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use tauri::{
-    ipc::{CommandScope, GlobalScope},
+    ipc::{Channel, CommandScope, GlobalScope},
     path::BaseDirectory,
     utils::config::FsScope,
     Manager, Resource, ResourceId, Runtime, Webview,
@@ -15,14 +15,17 @@ use tauri::{
 use std::{
     borrow::Cow,
     fs::File,
-    io::{BufReader, Lines, Read, Write},
-    path::{Path, PathBuf},
+    io::{BufRead, BufReader, Lines, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
     str::FromStr,
-    sync::Mutex,
-    time::{SystemTime, UNIX_EPOCH},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use crate::{scope::Entry, Error, FsExt, SafeFilePath};
+use crate::{encoding::TextEncoding, scope::Entry, Error, FsExt, SafeFilePath};
 
 #[derive(Debug, thiserror::Error)]
 pub enum CommandError {
@@ -73,7 +76,7 @@ pub type CommandResult<T> = std::result::Result<T, CommandError>;
 #[derive(Debug, Default, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BaseOptions {
-    base_dir: Option<BaseDirectory>,
+    pub(crate) base_dir: Option<BaseDirectory>,
 }
 
 #[tauri::command]
@@ -195,6 +198,400 @@ pub async fn copy_file<R: Runtime>(
     Ok(())
 }
 
+/// What to do when a file of the copied directory already exists at the destination.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CopyConflict {
+    /// Fails the copy.
+    #[default]
+    Error,
+    /// Replaces the existing file.
+    Overwrite,
+    /// Keeps the existing file.
+    Skip,
+}
+
+/// How the symbolic links of the copied directory are handled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymlinkPolicy {
+    /// Creates the same symbolic links at the destination,
+    /// which must point to paths allowed by the scope from their new location.
+    #[default]
+    Preserve,
+    /// Copies the files and directories the symbolic links point to,
+    /// which must be allowed by the scope.
+    Follow,
+    /// Ignores the symbolic links.
+    Skip,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyDirOptions {
+    from_path_base_dir: Option<BaseDirectory>,
+    to_path_base_dir: Option<BaseDirectory>,
+    #[serde(default)]
+    on_conflict: CopyConflict,
+    #[serde(default)]
+    symlinks: SymlinkPolicy,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data")]
+pub enum CopyDirEvent {
+    #[serde(rename_all = "camelCase")]
+    Started {
+        total_files: u64,
+        total_bytes: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Progress {
+        path: PathBuf,
+        copied_files: u64,
+        copied_bytes: u64,
+        skipped: bool,
+    },
+    Finished,
+}
+
+#[tauri::command]
+pub async fn copy_dir<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    from_path: SafeFilePath,
+    to_path: SafeFilePath,
+    options: Option<CopyDirOptions>,
+    on_progress: Channel<CopyDirEvent>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let resolved_from_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        from_path,
+        options.from_path_base_dir,
+    )?;
+    let resolved_to_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        to_path,
+        options.to_path_base_dir,
+    )?;
+    let scope = fs_scope(&webview, &global_scope, &command_scope)?;
+
+    copy_dir_inner(
+        &resolved_from_path,
+        &resolved_to_path,
+        &options,
+        |path| scope.is_allowed(path),
+        |event| {
+            let _ = on_progress.send(event);
+        },
+    )
+    .map_err(|e| {
+        format!(
+            "failed to copy directory from path: {}, to path: {} with error: {e}",
+            resolved_from_path.display(),
+            resolved_to_path.display()
+        )
+        .into()
+    })
+}
+
+pub(crate) enum CopyEntryKind {
+    Dir,
+    File(u64),
+    Symlink(PathBuf),
+}
+
+pub(crate) struct CopyEntry {
+    /// Path relative to the copied directory
+    pub path: PathBuf,
+    pub kind: CopyEntryKind,
+}
+
+/// Lists the entries of the directory `root.join(dir)`, checking each of them against the scope.
+pub(crate) fn collect_copy_entries(
+    root: &Path,
+    dir: &Path,
+    symlinks: SymlinkPolicy,
+    is_allowed: &impl Fn(&Path) -> bool,
+    visited: &mut Vec<PathBuf>,
+    entries: &mut Vec<CopyEntry>,
+) -> crate::Result<()> {
+    for entry in std::fs::read_dir(root.join(dir))? {
+        let entry = entry?;
+        let path = dir.join(entry.file_name());
+        let source = root.join(&path);
+        if !is_allowed(&source) {
+            return Err(Error::PathForbidden(source));
+        }
+
+        let file_type = entry.file_type()?;
+        let metadata = if file_type.is_symlink() {
+            match symlinks {
+                SymlinkPolicy::Skip => continue,
+                SymlinkPolicy::Preserve => {
+                    entries.push(CopyEntry {
+                        path,
+                        kind: CopyEntryKind::Symlink(std::fs::read_link(&source)?),
+                    });
+                    continue;
+                }
+                SymlinkPolicy::Follow => {
+                    // the link target must be in scope too
+                    let target = dunce::canonicalize(&source)?;
+                    if !is_allowed(&target) {
+                        return Err(Error::PathForbidden(target));
+                    }
+                    std::fs::metadata(&target)?
+                }
+            }
+        } else {
+            entry.metadata()?
+        };
+
+        if metadata.is_dir() {
+            // following symbolic links can lead to cycles
+            let canonical = dunce::canonicalize(&source)?;
+            if visited.contains(&canonical) {
+                continue;
+            }
+            visited.push(canonical);
+
+            entries.push(CopyEntry {
+                path: path.clone(),
+                kind: CopyEntryKind::Dir,
+            });
+            collect_copy_entries(root, &path, symlinks, is_allowed, visited, entries)?;
+            visited.pop();
+        } else {
+            entries.push(CopyEntry {
+                path,
+                kind: CopyEntryKind::File(metadata.len()),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn copy_dir_inner(
+    from: &Path,
+    to: &Path,
+    options: &CopyDirOptions,
+    is_allowed: impl Fn(&Path) -> bool,
+    mut on_progress: impl FnMut(CopyDirEvent),
+) -> crate::Result<()> {
+    if !from.is_dir() {
+        return Err(std::io::Error::other("source path is not a directory").into());
+    }
+    // copying a directory into itself never ends
+    let canonical_from = dunce::canonicalize(from)?;
+    if to.starts_with(from)
+        || dunce::canonicalize(to).is_ok_and(|to| to.starts_with(&canonical_from))
+    {
+        return Err(std::io::Error::other("cannot copy a directory into itself").into());
+    }
+
+    let mut entries = Vec::new();
+    collect_copy_entries(
+        from,
+        Path::new(""),
+        options.symlinks,
+        &is_allowed,
+        &mut vec![canonical_from],
+        &mut entries,
+    )?;
+    for entry in &entries {
+        let destination = to.join(&entry.path);
+        if !is_allowed(&destination) {
+            return Err(Error::PathForbidden(destination));
+        }
+    }
+
+    let (total_files, total_bytes) =
+        entries
+            .iter()
+            .fold((0, 0), |(files, bytes), entry| match entry.kind {
+                CopyEntryKind::Dir => (files, bytes),
+                CopyEntryKind::File(len) => (files + 1, bytes + len),
+                CopyEntryKind::Symlink(_) => (files + 1, bytes),
+            });
+    on_progress(CopyDirEvent::Started {
+        total_files,
+        total_bytes,
+    });
+
+    std::fs::create_dir_all(to)?;
+
+    let mut copied_files = 0;
+    let mut copied_bytes = 0;
+    let mut links = Vec::new();
+    for entry in entries {
+        let source = from.join(&entry.path);
+        let destination = to.join(&entry.path);
+
+        let len = match &entry.kind {
+            CopyEntryKind::Dir => {
+                std::fs::create_dir_all(&destination)?;
+                continue;
+            }
+            CopyEntryKind::File(len) => *len,
+            CopyEntryKind::Symlink(_) => 0,
+        };
+
+        let exists = std::fs::symlink_metadata(&destination).is_ok();
+        let skipped = exists && options.on_conflict == CopyConflict::Skip;
+        if exists && options.on_conflict == CopyConflict::Error {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("{} already exists", destination.display()),
+            )
+            .into());
+        }
+
+        if !skipped {
+            match &entry.kind {
+                CopyEntryKind::Symlink(target) => {
+                    check_link_target(&destination, target, &is_allowed)?;
+                    if exists {
+                        std::fs::remove_file(&destination)?;
+                    }
+                    create_symlink(target, &source, &destination)?;
+                    links.push((destination, target.clone()));
+                }
+                _ => {
+                    std::fs::copy(&source, &destination)?;
+                }
+            }
+        }
+
+        copied_files += 1;
+        copied_bytes += len;
+        on_progress(CopyDirEvent::Progress {
+            path: entry.path,
+            copied_files,
+            copied_bytes,
+            skipped,
+        });
+    }
+
+    // a link can point through links created after it
+    for (link, target) in links {
+        if let Err(e) = check_link_target(&link, &target, &is_allowed) {
+            std::fs::remove_file(&link)?;
+            return Err(e);
+        }
+    }
+
+    on_progress(CopyDirEvent::Finished);
+
+    Ok(())
+}
+
+/// Resolves `path` the way the file system does, following the symbolic links
+/// among its existing components and normalizing the rest of the path.
+pub(crate) fn resolve_symlinks(path: &Path) -> std::io::Result<PathBuf> {
+    resolve_symlinks_inner(path, 0)
+}
+
+fn resolve_symlinks_inner(path: &Path, depth: u32) -> std::io::Result<PathBuf> {
+    // same limit as Linux
+    if depth > 40 {
+        return Err(std::io::Error::other("too many levels of symbolic links"));
+    }
+
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::Normal(name) => {
+                resolved.push(name);
+                if std::fs::symlink_metadata(&resolved).is_ok_and(|m| m.is_symlink()) {
+                    let target = std::fs::read_link(&resolved)?;
+                    resolved.pop();
+                    resolved = resolve_symlinks_inner(&resolved.join(target), depth + 1)?;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => resolved.push(component),
+        }
+    }
+    Ok(resolved)
+}
+
+/// Resolves the target of the symbolic link at `link` from the directory containing it.
+pub(crate) fn resolve_link_target(link: &Path, target: &Path) -> std::io::Result<PathBuf> {
+    resolve_symlinks(&link.parent().unwrap_or(link).join(target))
+}
+
+/// Checks that the symbolic link at `link` pointing to `target` resolves to a path allowed by the scope.
+fn check_link_target(
+    link: &Path,
+    target: &Path,
+    is_allowed: &impl Fn(&Path) -> bool,
+) -> crate::Result<()> {
+    let resolved = resolve_link_target(link, target)?;
+    if is_allowed(&resolved) {
+        Ok(())
+    } else {
+        Err(Error::PathForbidden(resolved))
+    }
+}
+
+/// Checks the symbolic link at `link` against the scope without following it,
+/// unlike [`tauri::scope::fs::Scope::is_allowed`] which checks the target of a link.
+fn is_link_allowed<R: Runtime>(
+    webview: &Webview<R>,
+    scope: &tauri::scope::fs::Scope,
+    link: &Path,
+) -> bool {
+    // the parent directories are canonicalized like the scope patterns are
+    let path = match (link.parent(), link.file_name()) {
+        (Some(parent), Some(name)) => match std::fs::canonicalize(parent) {
+            Ok(parent) => parent.join(name),
+            Err(_) => return false,
+        },
+        _ => link.to_path_buf(),
+    };
+    let path: PathBuf = path.components().collect();
+
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        // same default as the scope, dotfiles are not exposed on Unix
+        require_literal_leading_dot: webview
+            .fs_scope()
+            .require_literal_leading_dot
+            .unwrap_or(cfg!(unix)),
+        ..Default::default()
+    };
+    let matches = |pattern: &glob::Pattern| pattern.matches_path_with(&path, options);
+    !scope.forbidden_patterns().iter().any(matches) && scope.allowed_patterns().iter().any(matches)
+}
+
+/// Creates a symbolic link at `link` pointing to `target`, like the one at `source`.
+#[allow(unused_variables)]
+pub(crate) fn create_symlink(target: &Path, source: &Path, link: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, link)
+    }
+    #[cfg(windows)]
+    {
+        if std::fs::metadata(source).is_ok_and(|m| m.is_dir()) {
+            std::os::windows::fs::symlink_dir(target, link)
+        } else {
+            std::os::windows::fs::symlink_file(target, link)
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct MkdirOptions {
     #[serde(flatten)]
@@ -241,7 +638,7 @@ pub fn mkdir<R: Runtime>(
         .map_err(Into::into)
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub struct DirEntry {
@@ -369,13 +766,258 @@ pub async fn read_file<R: Runtime>(
     Ok(tauri::ipc::Response::new(contents))
 }
 
+/// Size of the chunks read when hashing a file.
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+/// Number of bytes hashed between two progress events.
+const HASH_PROGRESS_INTERVAL: u64 = 1024 * 1024;
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha1,
+    Blake3,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashOptions {
+    #[serde(flatten)]
+    base: BaseOptions,
+    #[serde(default)]
+    algorithm: HashAlgorithm,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data")]
+pub enum HashEvent {
+    #[serde(rename_all = "camelCase")]
+    Progress {
+        hashed_bytes: u64,
+        total_bytes: Option<u64>,
+    },
+    /// The hex encoded digest
+    Finished {
+        hash: String,
+    },
+    Cancelled,
+    Error {
+        message: String,
+    },
+}
+
+enum Hasher {
+    Sha256(sha2::Sha256),
+    Sha1(sha1::Sha1),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        use sha2::Digest;
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Sha1 => Self::Sha1(sha1::Sha1::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::default()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        use sha2::Digest;
+        match self {
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Sha1(hasher) => hasher.update(data),
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    fn finalize(self) -> String {
+        use sha2::Digest;
+        let digest = match self {
+            Self::Sha256(hasher) => hasher.finalize().to_vec(),
+            Self::Sha1(hasher) => hasher.finalize().to_vec(),
+            Self::Blake3(hasher) => return hasher.finalize().to_hex().to_string(),
+        };
+        digest.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+/// Hashes `reader` until its end, resolves to `None` if `cancelled` is set in the meantime.
+fn hash_reader(
+    mut reader: impl Read,
+    algorithm: HashAlgorithm,
+    cancelled: &AtomicBool,
+    mut on_progress: impl FnMut(u64),
+) -> std::io::Result<Option<String>> {
+    let mut hasher = Hasher::new(algorithm);
+    let mut buf = vec![0; HASH_CHUNK_SIZE];
+    let mut hashed_bytes = 0;
+    let mut reported_bytes = 0;
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buf[..n]);
+        hashed_bytes += n as u64;
+        if hashed_bytes - reported_bytes >= HASH_PROGRESS_INTERVAL {
+            reported_bytes = hashed_bytes;
+            on_progress(hashed_bytes);
+        }
+    }
+    if reported_bytes != hashed_bytes {
+        on_progress(hashed_bytes);
+    }
+    Ok(Some(hasher.finalize()))
+}
+
+struct HashResource(Arc<AtomicBool>);
+
+impl Resource for HashResource {}
+
+/// Runs `hash` on a new thread, reporting its outcome to `on_event`.
+/// The returned resource cancels the hashing with [`cancel_hash`].
+fn spawn_hash<R: Runtime>(
+    webview: Webview<R>,
+    total_bytes: Option<u64>,
+    on_event: Channel<HashEvent>,
+    hash: impl FnOnce(&AtomicBool, &mut dyn FnMut(u64)) -> std::io::Result<Option<String>>
+        + Send
+        + 'static,
+) -> ResourceId {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let rid = webview
+        .resources_table()
+        .add(HashResource(cancelled.clone()));
+
+    std::thread::spawn(move || {
+        let event = match hash(&cancelled, &mut |hashed_bytes| {
+            let _ = on_event.send(HashEvent::Progress {
+                hashed_bytes,
+                total_bytes,
+            });
+        }) {
+            Ok(Some(hash)) => HashEvent::Finished { hash },
+            Ok(None) => HashEvent::Cancelled,
+            Err(e) => HashEvent::Error {
+                message: format!("failed to hash file with error: {e}"),
+            },
+        };
+        let _ = on_event.send(event);
+        let _ = webview.resources_table().close(rid);
+    });
+
+    rid
+}
+
+#[tauri::command]
+pub async fn hash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<HashOptions>,
+    on_event: Channel<HashEvent>,
+) -> CommandResult<ResourceId> {
+    let (file, path) = resolve_file(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        OpenOptions {
+            base: BaseOptions {
+                base_dir: options.as_ref().and_then(|o| o.base.base_dir),
+            },
+            options: crate::OpenOptions {
+                read: true,
+                ..Default::default()
+            },
+        },
+    )?;
+    let algorithm = options.map(|o| o.algorithm).unwrap_or_default();
+    let total_bytes = file
+        .metadata()
+        .map_err(|e| {
+            format!(
+                "failed to get metadata of path: {} with error: {e}",
+                path.display()
+            )
+        })?
+        .len();
+
+    Ok(spawn_hash(
+        webview,
+        Some(total_bytes),
+        on_event,
+        move |cancelled, on_progress| {
+            hash_reader(BufReader::new(file), algorithm, cancelled, on_progress)
+        },
+    ))
+}
+
+#[tauri::command]
+pub async fn fhash<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+    algorithm: Option<HashAlgorithm>,
+    on_event: Channel<HashEvent>,
+) -> CommandResult<ResourceId> {
+    let file = webview.resources_table().get::<StdFileResource>(rid)?;
+    let total_bytes = StdFileResource::with_lock(&file, |file| file.metadata())
+        .map(|metadata| metadata.len())
+        .ok();
+    let algorithm = algorithm.unwrap_or_default();
+
+    Ok(spawn_hash(
+        webview,
+        total_bytes,
+        on_event,
+        move |cancelled, on_progress| {
+            // the whole file is hashed, the handle is locked meanwhile so its position can be restored
+            StdFileResource::with_lock(&file, |mut file| {
+                let position = file.stream_position()?;
+                file.seek(SeekFrom::Start(0))?;
+                let hash = hash_reader(file, algorithm, cancelled, &mut *on_progress);
+                file.seek(SeekFrom::Start(position))?;
+                hash
+            })
+        },
+    ))
+}
+
+#[tauri::command]
+pub fn cancel_hash<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> CommandResult<()> {
+    // the hashing may have already finished
+    if let Ok(hash) = webview.resources_table().take::<HashResource>(rid) {
+        hash.0.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadTextFileOptions {
+    #[serde(flatten)]
+    base: BaseOptions,
+    /// Strict UTF-8 when not set
+    encoding: Option<TextEncoding>,
+}
+
 #[tauri::command]
 pub async fn read_text_file<R: Runtime>(
     webview: Webview<R>,
     global_scope: GlobalScope<Entry>,
     command_scope: CommandScope<Entry>,
     path: SafeFilePath,
-    options: Option<BaseOptions>,
+    options: Option<ReadTextFileOptions>,
 ) -> CommandResult<String> {
     let (mut file, path) = resolve_file(
         &webview,
@@ -384,7 +1026,7 @@ pub async fn read_text_file<R: Runtime>(
         path,
         OpenOptions {
             base: BaseOptions {
-                base_dir: options.as_ref().and_then(|o| o.base_dir),
+                base_dir: options.as_ref().and_then(|o| o.base.base_dir),
             },
             options: crate::OpenOptions {
                 read: true,
@@ -393,14 +1035,21 @@ pub async fn read_text_file<R: Runtime>(
         },
     )?;
 
-    let mut contents = String::new();
-
-    file.read_to_string(&mut contents).map_err(|e| {
+    let read_error = |e: std::io::Error| {
         format!(
             "failed to read file as text at path: {} with error: {e}",
             path.display()
         )
-    })?;
+    };
+
+    if let Some(encoding) = options.and_then(|o| o.encoding) {
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents).map_err(read_error)?;
+        return Ok(crate::encoding::decode(&contents, encoding));
+    }
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).map_err(read_error)?;
 
     Ok(contents)
 }
@@ -411,16 +1060,14 @@ pub fn read_text_file_lines<R: Runtime>(
     global_scope: GlobalScope<Entry>,
     command_scope: CommandScope<Entry>,
     path: SafeFilePath,
-    options: Option<BaseOptions>,
+    options: Option<ReadTextFileOptions>,
 ) -> CommandResult<ResourceId> {
-    use std::io::BufRead;
-
     let resolved_path = resolve_path(
         &webview,
         &global_scope,
         &command_scope,
         path,
-        options.as_ref().and_then(|o| o.base_dir),
+        options.as_ref().and_then(|o| o.base.base_dir),
     )?;
 
     let file = File::open(&resolved_path).map_err(|e| {
@@ -430,8 +1077,18 @@ pub fn read_text_file_lines<R: Runtime>(
         )
     })?;
 
-    let lines = BufReader::new(file).lines();
-    let rid = webview.resources_table().add(StdLinesResource::new(lines));
+    let reader: Box<dyn BufRead + Send> = match options.and_then(|o| o.encoding) {
+        Some(encoding) => crate::encoding::decode_reader(file, encoding).map_err(|e| {
+            format!(
+                "failed to read file as text at path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })?,
+        None => Box::new(BufReader::new(file)),
+    };
+    let rid = webview
+        .resources_table()
+        .add(StdLinesResource::new(reader.lines()));
 
     Ok(rid)
 }
@@ -578,7 +1235,6 @@ pub async fn seek<R: Runtime>(
     offset: i64,
     whence: SeekMode,
 ) -> CommandResult<u64> {
-    use std::io::{Seek, SeekFrom};
     let file = webview.resources_table().get::<StdFileResource>(rid)?;
     StdFileResource::with_lock(&file, |mut file| {
         file.seek(match whence {
@@ -724,6 +1380,222 @@ pub fn fstat<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> CommandResult<
     Ok(get_stat(metadata))
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkOptions {
+    target_base_dir: Option<BaseDirectory>,
+    link_base_dir: Option<BaseDirectory>,
+}
+
+#[tauri::command]
+pub fn symlink<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    target: SafeFilePath,
+    link: SafeFilePath,
+    options: Option<LinkOptions>,
+) -> CommandResult<()> {
+    let resolved_link = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        link,
+        options.as_ref().and_then(|o| o.link_base_dir),
+    )?;
+
+    // the target is written as given, a relative target is resolved from the directory of the link
+    let target = target.into_path()?;
+    let target = match options.as_ref().and_then(|o| o.target_base_dir) {
+        Some(base_dir) => webview.path().resolve(&target, base_dir)?,
+        None => target,
+    };
+    let scope = fs_scope(&webview, &global_scope, &command_scope)?;
+    check_link_target(&resolved_link, &target, &|path| scope.is_allowed(path))?;
+
+    let source = resolve_link_target(&resolved_link, &target)?;
+    create_symlink(&target, &source, &resolved_link)
+        .map_err(|e| {
+            format!(
+                "failed to create symlink at path: {} to target: {} with error: {e}",
+                resolved_link.display(),
+                target.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn link<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    target: SafeFilePath,
+    link: SafeFilePath,
+    options: Option<LinkOptions>,
+) -> CommandResult<()> {
+    let resolved_target = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        target,
+        options.as_ref().and_then(|o| o.target_base_dir),
+    )?;
+    let resolved_link = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        link,
+        options.as_ref().and_then(|o| o.link_base_dir),
+    )?;
+    std::fs::hard_link(&resolved_target, &resolved_link)
+        .map_err(|e| {
+            format!(
+                "failed to create hard link at path: {} to target: {} with error: {e}",
+                resolved_link.display(),
+                resolved_target.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn read_link<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<PathBuf> {
+    let path = path.into_path()?;
+    let resolved_path = match options.and_then(|o| o.base_dir) {
+        Some(base_dir) => webview.path().resolve(&path, base_dir)?,
+        None => path,
+    };
+
+    // only the link is checked, its target is returned without being accessed
+    let scope = fs_scope(&webview, &global_scope, &command_scope)?;
+    if !is_link_allowed(&webview, &scope, &resolved_path) {
+        return Err(CommandError::Plugin(Error::PathForbidden(resolved_path)));
+    }
+
+    std::fs::read_link(&resolved_path)
+        .map_err(|e| {
+            format!(
+                "failed to read link at path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+/// Sets the permission bits of `path`.
+///
+/// Only the owner write bit is used on Windows, where it toggles the read-only attribute.
+pub(crate) fn set_mode(path: &Path, mode: u32) -> std::io::Result<()> {
+    #[cfg(unix)]
+    let permissions = {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::Permissions::from_mode(mode)
+    };
+    #[cfg(not(unix))]
+    let permissions = {
+        let mut permissions = std::fs::metadata(path)?.permissions();
+        permissions.set_readonly(mode & 0o200 == 0);
+        permissions
+    };
+    std::fs::set_permissions(path, permissions)
+}
+
+#[tauri::command]
+pub fn chmod<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    mode: u32,
+    options: Option<BaseOptions>,
+) -> CommandResult<()> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+    set_mode(&resolved_path, mode)
+        .map_err(|e| {
+            format!(
+                "failed to change the permissions of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+/// Sets the access and modification times of `path`, in milliseconds since the Unix epoch.
+pub(crate) fn set_times(path: &Path, atime: i64, mtime: i64) -> std::io::Result<()> {
+    let to_file_time = |msec: i64| {
+        filetime::FileTime::from_unix_time(
+            msec.div_euclid(1000),
+            (msec.rem_euclid(1000) * 1_000_000) as u32,
+        )
+    };
+    filetime::set_file_times(path, to_file_time(atime), to_file_time(mtime))
+}
+
+#[tauri::command]
+pub fn utime<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    atime: i64,
+    mtime: i64,
+    options: Option<BaseOptions>,
+) -> CommandResult<()> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+    set_times(&resolved_path, atime, mtime)
+        .map_err(|e| {
+            format!(
+                "failed to change the times of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn volume_info<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<crate::volume::VolumeInfo> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+    crate::volume::volume_info(&resolved_path)
+        .map_err(|e| {
+            format!(
+                "failed to get the volume information of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
 #[tauri::command]
 pub async fn truncate<R: Runtime>(
     webview: Webview<R>,
@@ -771,6 +1643,72 @@ pub async fn ftruncate<R: Runtime>(
         .map_err(Into::into)
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockMode {
+    /// Only one handle can hold the lock
+    #[default]
+    Exclusive,
+    /// Any number of handles can hold the lock, as long as no one holds it exclusively
+    Shared,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockOptions {
+    #[serde(default)]
+    mode: LockMode,
+    /// Gives up after this many milliseconds instead of waiting for the lock indefinitely
+    timeout_ms: Option<u64>,
+}
+
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+// `File` has inherent locking methods with other signatures on recent toolchains,
+// so the `fs4` ones are called explicitly
+fn try_lock_file(file: &File, mode: LockMode) -> std::io::Result<bool> {
+    match mode {
+        LockMode::Exclusive => fs4::fs_std::FileExt::try_lock_exclusive(file),
+        LockMode::Shared => fs4::fs_std::FileExt::try_lock_shared(file),
+    }
+}
+
+#[tauri::command]
+pub async fn lock<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+    options: Option<LockOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let file = webview.resources_table().get::<StdFileResource>(rid)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        file.lock(options.mode, options.timeout_ms.map(Duration::from_millis))
+    })
+    .await?
+    .map_err(|e| format!("failed to lock file with error: {e}"))
+    .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn try_lock<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+    mode: Option<LockMode>,
+) -> CommandResult<bool> {
+    let file = webview.resources_table().get::<StdFileResource>(rid)?;
+    StdFileResource::with_lock(&file, |file| try_lock_file(file, mode.unwrap_or_default()))
+        .map_err(|e| format!("failed to lock file with error: {e}"))
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn unlock<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> CommandResult<()> {
+    let file = webview.resources_table().get::<StdFileResource>(rid)?;
+    StdFileResource::with_lock(&file, fs4::fs_std::FileExt::unlock)
+        .map_err(|e| format!("failed to unlock file with error: {e}"))
+        .map_err(Into::into)
+}
+
 #[tauri::command]
 pub async fn write<R: Runtime>(
     webview: Webview<R>,
@@ -796,6 +1734,17 @@ pub struct WriteFileOptions {
     create_new: bool,
     #[allow(unused)]
     mode: Option<u32>,
+    /// Write to a temporary file renamed over the target once fully written
+    #[serde(default)]
+    atomic: bool,
+    /// Flush the written data to the disk before returning
+    #[serde(default)]
+    sync: bool,
+    /// Encoding of the file written by `write_text_file`, UTF-8 when not set
+    encoding: Option<TextEncoding>,
+    /// Start the text with a byte order mark, for the Unicode encodings
+    #[serde(default)]
+    bom: bool,
 }
 
 fn default_create_value() -> bool {
@@ -807,8 +1756,9 @@ fn write_file_inner<R: Runtime>(
     global_scope: &GlobalScope<Entry>,
     command_scope: &CommandScope<Entry>,
     request: tauri::ipc::Request<'_>,
+    is_text: bool,
 ) -> CommandResult<()> {
-    let data = match request.body() {
+    let mut data = match request.body() {
         tauri::ipc::InvokeBody::Raw(data) => Cow::Borrowed(data),
         tauri::ipc::InvokeBody::Json(serde_json::Value::Array(data)) => Cow::Owned(
             data.iter()
@@ -831,9 +1781,69 @@ fn write_file_inner<R: Runtime>(
     let options: Option<WriteFileOptions> = request
         .headers()
         .get("options")
-        .and_then(|p| p.to_str().ok())
-        .and_then(|opts| serde_json::from_str(opts).ok());
+        .map(|opts| {
+            let opts = opts
+                .to_str()
+                .map_err(|_| anyhow::anyhow!("options are not a valid UTF-8"))?;
+            serde_json::from_str(opts).map_err(|e| anyhow::anyhow!("invalid options: {e}"))
+        })
+        .transpose()?;
+
+    // the text is sent as UTF-8
+    if let Some(opts) = options
+        .as_ref()
+        .filter(|opts| is_text && (opts.encoding.is_some() || opts.bom))
+    {
+        let text = std::str::from_utf8(&data).map_err(|e| anyhow::anyhow!("invalid text: {e}"))?;
+        let encoding = match opts.encoding {
+            Some(TextEncoding::Label(encoding)) => encoding,
+            // the encoding of the existing file
+            Some(TextEncoding::Auto) => {
+                let path = resolve_path(
+                    &webview,
+                    global_scope,
+                    command_scope,
+                    path.clone(),
+                    opts.base.base_dir,
+                )?;
+                crate::encoding::detect_file(&path)?
+            }
+            None => encoding_rs::UTF_8,
+        };
+        // appended text goes after the byte order mark, if any
+        let bom = opts.bom && !opts.append;
+        data = Cow::Owned(crate::encoding::encode(text, encoding, bom)?);
+    }
 
+    if let Some(opts) = options.as_ref().filter(|opts| opts.atomic) {
+        let path = resolve_path(
+            &webview,
+            global_scope,
+            command_scope,
+            path,
+            opts.base.base_dir,
+        )?;
+        return crate::atomic::write(
+            &path,
+            &data,
+            crate::atomic::AtomicWriteOptions {
+                append: opts.append,
+                create: opts.create,
+                create_new: opts.create_new,
+                mode: opts.mode,
+                sync: opts.sync,
+            },
+        )
+        .map_err(|e| {
+            format!(
+                "failed to write bytes to file at path: {} with error: {e}",
+                path.display()
+            )
+        })
+        .map_err(Into::into);
+    }
+
+    let sync = options.as_ref().is_some_and(|opts| opts.sync);
     let (mut file, path) = resolve_file(
         &webview,
         global_scope,
@@ -871,6 +1881,7 @@ fn write_file_inner<R: Runtime>(
     )?;
 
     file.write_all(&data)
+        .and_then(|()| if sync { file.sync_all() } else { Ok(()) })
         .map_err(|e| {
             format!(
                 "failed to write bytes to file at path: {} with error: {e}",
@@ -887,7 +1898,7 @@ pub async fn write_file<R: Runtime>(
     command_scope: CommandScope<Entry>,
     request: tauri::ipc::Request<'_>,
 ) -> CommandResult<()> {
-    write_file_inner(webview, &global_scope, &command_scope, request)
+    write_file_inner(webview, &global_scope, &command_scope, request, false)
 }
 
 // TODO, in v3, remove this command and rely on `write_file` command only
@@ -898,7 +1909,7 @@ pub async fn write_text_file<R: Runtime>(
     command_scope: CommandScope<Entry>,
     request: tauri::ipc::Request<'_>,
 ) -> CommandResult<()> {
-    write_file_inner(webview, &global_scope, &command_scope, request)
+    write_file_inner(webview, &global_scope, &command_scope, request, true)
 }
 
 #[tauri::command]
@@ -996,7 +2007,23 @@ pub fn resolve_path<R: Runtime>(
         path
     };
 
-    let scope = tauri::scope::fs::Scope::new(
+    let scope = fs_scope(webview, global_scope, command_scope)?;
+
+    if scope.is_allowed(&path) {
+        Ok(path)
+    } else {
+        Err(CommandError::Plugin(Error::PathForbidden(path)))
+    }
+}
+
+/// The scope of the command, used to check paths that are not resolved with [`resolve_path`],
+/// e.g. the entries of a directory.
+pub fn fs_scope<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+) -> CommandResult<tauri::scope::fs::Scope> {
+    tauri::scope::fs::Scope::new(
         webview,
         &FsScope::Scope {
             allow: webview
@@ -1021,38 +2048,64 @@ pub fn resolve_path<R: Runtime>(
                 .collect(),
             require_literal_leading_dot: webview.fs_scope().require_literal_leading_dot,
         },
-    )?;
-
-    if scope.is_allowed(&path) {
-        Ok(path)
-    } else {
-        Err(CommandError::Plugin(Error::PathForbidden(path)))
-    }
+    )
+    .map_err(Into::into)
 }
 
-struct StdFileResource(Mutex<File>);
+/// An open file and whether its resource was closed.
+struct StdFileResource(Mutex<File>, AtomicBool);
 
 impl StdFileResource {
     fn new(file: File) -> Self {
-        Self(Mutex::new(file))
+        Self(Mutex::new(file), AtomicBool::new(false))
     }
 
     fn with_lock<R, F: FnMut(&File) -> R>(&self, mut f: F) -> R {
         let file = self.0.lock().unwrap();
         f(&file)
     }
+
+    /// Waits for the advisory lock of the file, which is released when the resource is closed.
+    ///
+    /// The lock is polled so the file stays usable by other commands in the meantime,
+    /// closing the resource stops waiting.
+    fn lock(&self, mode: LockMode, timeout: Option<Duration>) -> std::io::Result<()> {
+        let start = Instant::now();
+        loop {
+            if self.1.load(Ordering::Relaxed) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Interrupted,
+                    "the file was closed while waiting for its lock",
+                ));
+            }
+            if self.with_lock(|file| try_lock_file(file, mode))? {
+                return Ok(());
+            }
+            if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "timed out waiting for the file lock",
+                ));
+            }
+            std::thread::sleep(LOCK_RETRY_INTERVAL);
+        }
+    }
 }
 
-impl Resource for StdFileResource {}
+impl Resource for StdFileResource {
+    fn close(self: Arc<Self>) {
+        self.1.store(true, Ordering::Relaxed);
+    }
+}
 
-struct StdLinesResource(Mutex<Lines<BufReader<File>>>);
+struct StdLinesResource(Mutex<Lines<Box<dyn BufRead + Send>>>);
 
 impl StdLinesResource {
-    fn new(lines: Lines<BufReader<File>>) -> Self {
+    fn new(lines: Lines<Box<dyn BufRead + Send>>) -> Self {
         Self(Mutex::new(lines))
     }
 
-    fn with_lock<R, F: FnMut(&mut Lines<BufReader<File>>) -> R>(&self, mut f: F) -> R {
+    fn with_lock<R, F: FnMut(&mut Lines<Box<dyn BufRead + Send>>) -> R>(&self, mut f: F) -> R {
         let mut lines = self.0.lock().unwrap();
         f(&mut lines)
     }
@@ -1152,6 +2205,149 @@ fn get_stat(metadata: std::fs::Metadata) -> FileInfo {
 }
 
 mod test {
+    #[test]
+    fn copy_dir_recursively() {
+        use super::{copy_dir_inner, CopyConflict, CopyDirEvent, CopyDirOptions};
+
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        let from = root.join("from");
+        let to = root.join("to");
+        std::fs::create_dir_all(from.join("nested")).unwrap();
+        std::fs::write(from.join("a.txt"), "a").unwrap();
+        std::fs::write(from.join("nested/b.txt"), "bb").unwrap();
+
+        let mut events = Vec::new();
+        copy_dir_inner(
+            &from,
+            &to,
+            &CopyDirOptions::default(),
+            |_| true,
+            |e| events.push(e),
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(to.join("nested/b.txt")).unwrap(),
+            "bb"
+        );
+        assert!(matches!(
+            events.first(),
+            Some(CopyDirEvent::Started {
+                total_files: 2,
+                total_bytes: 3
+            })
+        ));
+        assert!(matches!(events.last(), Some(CopyDirEvent::Finished)));
+
+        // the files already exist
+        assert!(copy_dir_inner(&from, &to, &CopyDirOptions::default(), |_| true, |_| {}).is_err());
+
+        std::fs::write(from.join("a.txt"), "updated").unwrap();
+        let skip = CopyDirOptions {
+            on_conflict: CopyConflict::Skip,
+            ..Default::default()
+        };
+        copy_dir_inner(&from, &to, &skip, |_| true, |_| {}).unwrap();
+        assert_eq!(std::fs::read_to_string(to.join("a.txt")).unwrap(), "a");
+
+        let overwrite = CopyDirOptions {
+            on_conflict: CopyConflict::Overwrite,
+            ..Default::default()
+        };
+        copy_dir_inner(&from, &to, &overwrite, |_| true, |_| {}).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(to.join("a.txt")).unwrap(),
+            "updated"
+        );
+
+        // every entry is checked against the scope
+        let denied = from.join("nested");
+        assert!(copy_dir_inner(
+            &from,
+            &root.join("denied"),
+            &CopyDirOptions::default(),
+            |p| !p.starts_with(&denied),
+            |_| {}
+        )
+        .is_err());
+        assert!(!root.join("denied").exists());
+
+        // a directory can't be copied into itself
+        assert!(copy_dir_inner(
+            &from,
+            &from.join("nested/copy"),
+            &CopyDirOptions::default(),
+            |_| true,
+            |_| {}
+        )
+        .is_err());
+
+        // preserved links must point into the scope from their new location
+        #[cfg(unix)]
+        {
+            let links = root.join("links");
+            std::fs::create_dir_all(&links).unwrap();
+            std::os::unix::fs::symlink("a.txt", links.join("inside")).unwrap();
+            let in_scope = |p: &std::path::Path| p.starts_with(root);
+            copy_dir_inner(
+                &links,
+                &root.join("links-copy"),
+                &CopyDirOptions::default(),
+                in_scope,
+                |_| {},
+            )
+            .unwrap();
+
+            std::os::unix::fs::symlink("../..", links.join("escape")).unwrap();
+            assert!(copy_dir_inner(
+                &links,
+                &root.join("links-denied"),
+                &CopyDirOptions::default(),
+                in_scope,
+                |_| {}
+            )
+            .is_err());
+            assert!(!root.join("links-denied/escape").exists());
+        }
+    }
+
+    #[test]
+    fn hash_reader_digests() {
+        use super::{hash_reader, HashAlgorithm};
+        use std::sync::atomic::AtomicBool;
+
+        let not_cancelled = AtomicBool::new(false);
+        let hash = |algorithm| {
+            let mut progress = Vec::new();
+            let hash = hash_reader(&b"abc"[..], algorithm, &not_cancelled, |bytes| {
+                progress.push(bytes)
+            })
+            .unwrap();
+            assert_eq!(progress, [3]);
+            hash.unwrap()
+        };
+
+        assert_eq!(
+            hash(HashAlgorithm::Sha256),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hash(HashAlgorithm::Sha1),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            hash(HashAlgorithm::Blake3),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+
+        let cancelled = AtomicBool::new(true);
+        assert!(
+            hash_reader(&b"abc"[..], HashAlgorithm::Sha256, &cancelled, |_| {})
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[test]
     fn safe_file_path_parse() {
         use super::SafeFilePath;
@@ -1165,4 +2361,102 @@ mod test {
             Ok(SafeFilePath::Url(_))
         ));
     }
+
+    #[test]
+    fn file_locks_conflict() {
+        use super::{try_lock_file, LockMode, Resource, StdFileResource};
+        use std::{fs::File, io::ErrorKind, sync::Arc, time::Duration};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.lock");
+        std::fs::write(&path, "").unwrap();
+
+        let first = StdFileResource::new(File::open(&path).unwrap());
+        let second = StdFileResource::new(File::open(&path).unwrap());
+
+        first.lock(LockMode::Shared, None).unwrap();
+        assert!(second.with_lock(|file| try_lock_file(file, LockMode::Shared).unwrap()));
+        assert_eq!(
+            first
+                .lock(LockMode::Exclusive, Some(Duration::from_millis(30)))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::TimedOut
+        );
+
+        // closing the resource releases its lock
+        drop(second);
+        first
+            .lock(LockMode::Exclusive, Some(Duration::ZERO))
+            .unwrap();
+        let third = StdFileResource::new(File::open(&path).unwrap());
+        assert!(!third.with_lock(|file| try_lock_file(file, LockMode::Shared).unwrap()));
+
+        // closing the resource stops waiting for the lock
+        let third = Arc::new(third);
+        let waiting = std::thread::spawn({
+            let third = third.clone();
+            move || third.lock(LockMode::Shared, None)
+        });
+        Resource::close(third);
+        assert_eq!(
+            waiting.join().unwrap().unwrap_err().kind(),
+            ErrorKind::Interrupted
+        );
+
+        drop(first);
+    }
+
+    #[test]
+    fn links_modes_and_times() {
+        use super::{
+            create_symlink, resolve_link_target, resolve_symlinks, set_mode, set_times, to_msec,
+        };
+        use std::path::Path;
+
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let target = dir.join("target.txt");
+        std::fs::write(&target, "data").unwrap();
+
+        let link = dir.join("link.txt");
+        std::fs::hard_link(&target, &link).unwrap();
+        assert_eq!(std::fs::read(&link).unwrap(), b"data");
+
+        #[cfg(unix)]
+        {
+            let symlink = dir.join("symlink.txt");
+            create_symlink(&target, &target, &symlink).unwrap();
+            assert_eq!(std::fs::read_link(&symlink).unwrap(), target);
+
+            // relative targets are kept and resolved from the directory of the link
+            std::fs::create_dir(dir.join("nested")).unwrap();
+            let relative = dir.join("nested/symlink.txt");
+            let source = resolve_link_target(&relative, Path::new("../target.txt")).unwrap();
+            assert_eq!(source, resolve_symlinks(&target).unwrap());
+            create_symlink(Path::new("../target.txt"), &source, &relative).unwrap();
+            assert_eq!(
+                std::fs::read_link(&relative).unwrap(),
+                Path::new("../target.txt")
+            );
+            assert_eq!(std::fs::read(&relative).unwrap(), b"data");
+
+            use std::os::unix::fs::PermissionsExt;
+            set_mode(&target, 0o640).unwrap();
+            let mode = std::fs::metadata(&target).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o640);
+        }
+
+        set_mode(&target, 0o444).unwrap();
+        assert!(std::fs::metadata(&target).unwrap().permissions().readonly());
+        set_mode(&target, 0o644).unwrap();
+
+        set_times(&target, 1_000_500, -1_500).unwrap();
+        let metadata = std::fs::metadata(&target).unwrap();
+        assert_eq!(to_msec(metadata.accessed()), Some(1_000_500));
+        assert_eq!(
+            metadata.modified().unwrap(),
+            std::time::UNIX_EPOCH - std::time::Duration::from_millis(1_500)
+        );
+    }
 }
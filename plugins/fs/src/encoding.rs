@@ -0,0 +1,197 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Text encodings of the text reading and writing commands, identified by their
+//! [WHATWG labels](https://encoding.spec.whatwg.org/#names-and-labels) such as `windows-1252` or `utf-16le`.
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use serde::{de::Error as DeError, Deserialize, Deserializer};
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Error, ErrorKind, Read, Result, Seek, SeekFrom},
+};
+
+/// Number of bytes looked at to guess the encoding of a text.
+const DETECT_SAMPLE_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    /// Guessed from the byte order mark or the contents
+    Auto,
+    Label(&'static Encoding),
+}
+
+impl<'de> Deserialize<'de> for TextEncoding {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let label = String::deserialize(deserializer)?;
+        if label.eq_ignore_ascii_case("auto") {
+            return Ok(Self::Auto);
+        }
+        Encoding::for_label(label.as_bytes())
+            .map(Self::Label)
+            .ok_or_else(|| DeError::custom(format!("unknown encoding: {label}")))
+    }
+}
+
+/// Guesses the encoding of `sample`, the start of a text or the whole text when `is_complete`.
+pub(crate) fn detect(sample: &[u8], is_complete: bool) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(sample) {
+        return encoding;
+    }
+
+    // mostly ASCII UTF-16 texts have a zero in every other byte
+    let zeros_at = |parity: usize| {
+        sample
+            .iter()
+            .skip(parity)
+            .step_by(2)
+            .filter(|b| **b == 0)
+            .count()
+    };
+    let half = sample.len() / 2;
+    if half > 0 {
+        let (even, odd) = (zeros_at(0), zeros_at(1));
+        if odd * 3 > half && even * 20 < half {
+            return UTF_16LE;
+        }
+        if even * 3 > half && odd * 20 < half {
+            return UTF_16BE;
+        }
+    }
+
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(sample, is_complete);
+    detector.guess(None, true)
+}
+
+fn resolve(bytes: &[u8], encoding: TextEncoding, is_complete: bool) -> &'static Encoding {
+    match encoding {
+        TextEncoding::Auto => detect(bytes, is_complete),
+        TextEncoding::Label(encoding) => encoding,
+    }
+}
+
+/// Decodes `bytes`, malformed sequences are replaced by `U+FFFD`.
+///
+/// A byte order mark takes precedence over `encoding` and is removed.
+pub(crate) fn decode(bytes: &[u8], encoding: TextEncoding) -> String {
+    let (text, _, _) = resolve(bytes, encoding, true).decode(bytes);
+    text.into_owned()
+}
+
+/// Reads `file` from its current position, decoding it to UTF-8 on the fly.
+pub(crate) fn decode_reader(
+    mut file: File,
+    encoding: TextEncoding,
+) -> Result<Box<dyn BufRead + Send>> {
+    let encoding = match encoding {
+        TextEncoding::Auto => {
+            let start = file.stream_position()?;
+            let mut sample = Vec::with_capacity(DETECT_SAMPLE_SIZE);
+            (&mut file)
+                .take(DETECT_SAMPLE_SIZE as u64)
+                .read_to_end(&mut sample)?;
+            file.seek(SeekFrom::Start(start))?;
+            detect(&sample, sample.len() < DETECT_SAMPLE_SIZE)
+        }
+        TextEncoding::Label(encoding) => encoding,
+    };
+    let reader = DecodeReaderBytesBuilder::new()
+        .encoding(Some(encoding))
+        .bom_override(true)
+        .build(file);
+    Ok(Box::new(BufReader::new(reader)))
+}
+
+/// Guesses the encoding of the file at `path` from its start, UTF-8 if it is empty or does not exist.
+pub(crate) fn detect_file(path: &std::path::Path) -> Result<&'static Encoding> {
+    let mut sample = Vec::with_capacity(DETECT_SAMPLE_SIZE);
+    match File::open(path) {
+        Ok(file) => file
+            .take(DETECT_SAMPLE_SIZE as u64)
+            .read_to_end(&mut sample)?,
+        Err(e) if e.kind() == ErrorKind::NotFound => 0,
+        Err(e) => return Err(e),
+    };
+    if sample.is_empty() {
+        return Ok(UTF_8);
+    }
+    Ok(detect(&sample, sample.len() < DETECT_SAMPLE_SIZE))
+}
+
+/// Encodes `text`, starting with a byte order mark when `bom` is set and the encoding is a Unicode one.
+///
+/// Fails if `text` contains characters that can't be represented in `encoding`.
+pub(crate) fn encode(text: &str, encoding: &'static Encoding, bom: bool) -> Result<Vec<u8>> {
+    // encoding_rs only decodes UTF-16, as the web never writes it
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        let to_bytes = if encoding == UTF_16LE {
+            u16::to_le_bytes
+        } else {
+            u16::to_be_bytes
+        };
+        let units = bom.then_some(0xFEFF).into_iter().chain(text.encode_utf16());
+        return Ok(units.flat_map(to_bytes).collect());
+    }
+
+    let (bytes, _, had_errors) = encoding.encode(text);
+    if had_errors {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "the text contains characters that can't be encoded in {}",
+                encoding.name()
+            ),
+        ));
+    }
+    let mut encoded = Vec::with_capacity(bytes.len() + 3);
+    if bom && encoding == UTF_8 {
+        encoded.extend_from_slice(b"\xEF\xBB\xBF");
+    }
+    encoded.extend_from_slice(&bytes);
+    Ok(encoded)
+}
+
+mod test {
+    #[test]
+    fn encodings_roundtrip_and_detection() {
+        use super::{decode, detect, encode, TextEncoding};
+        use encoding_rs::{UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+
+        let text = "Café;Crème brûlée;12,50 €\r\n";
+
+        let windows_1252 = encode(text, WINDOWS_1252, true).unwrap();
+        assert_eq!(&windows_1252[..4], b"Caf\xE9");
+        assert_eq!(
+            decode(&windows_1252, TextEncoding::Label(WINDOWS_1252)),
+            text
+        );
+        assert_eq!(decode(&windows_1252, TextEncoding::Auto), text);
+        assert!(encode("日本", WINDOWS_1252, false).is_err());
+
+        let utf16_bom = encode(text, UTF_16LE, true).unwrap();
+        assert_eq!(&utf16_bom[..4], b"\xFF\xFEC\0");
+        // the byte order mark wins over the requested encoding
+        assert_eq!(decode(&utf16_bom, TextEncoding::Label(UTF_8)), text);
+
+        let utf16 = encode(text, UTF_16BE, false).unwrap();
+        assert_eq!(detect(&utf16, true), UTF_16BE);
+        assert_eq!(decode(&utf16, TextEncoding::Auto), text);
+
+        let utf8 = encode(text, UTF_8, true).unwrap();
+        assert_eq!(decode(&utf8, TextEncoding::Auto), text);
+        assert_eq!(detect(text.as_bytes(), true), UTF_8);
+
+        assert_eq!(
+            serde_json::from_str::<TextEncoding>("\"latin1\"").unwrap(),
+            TextEncoding::Label(WINDOWS_1252)
+        );
+        assert!(serde_json::from_str::<TextEncoding>("\"klingon\"").is_err());
+    }
+}
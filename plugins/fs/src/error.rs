@@ -20,10 +20,37 @@ pub enum Error {
     /// Invalid glob pattern.
     #[error("invalid glob pattern: {0}")]
     GlobPattern(#[from] glob::PatternError),
+    /// Invalid walk glob pattern.
+    #[error("invalid glob pattern: {0}")]
+    GlobSet(#[from] globset::Error),
     /// Watcher error.
     #[cfg(feature = "watch")]
     #[error(transparent)]
     Watch(#[from] notify::Error),
+    /// Zip archive error.
+    #[cfg(feature = "archive")]
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+    /// An archive entry would be extracted out of the destination directory.
+    #[cfg(feature = "archive")]
+    #[error("unsafe archive entry: {0}")]
+    UnsafeArchiveEntry(PathBuf),
+    /// The archive format can't be guessed from the file extension.
+    #[cfg(feature = "archive")]
+    #[error("unknown archive format: {0}")]
+    UnknownArchiveFormat(PathBuf),
+    /// The path is not an item of the trash.
+    #[cfg(feature = "trash")]
+    #[error("not in the trash: {0}")]
+    NotInTrash(PathBuf),
+    /// The trash operation is not available on the current platform.
+    #[cfg(feature = "trash")]
+    #[error("{0} is not supported on this platform")]
+    TrashUnsupported(&'static str),
+    /// System trash error.
+    #[cfg(all(feature = "trash", any(target_os = "macos", windows)))]
+    #[error(transparent)]
+    Trash(#[from] trash::Error),
     #[cfg(target_os = "android")]
     #[error(transparent)]
     PluginInvoke(#[from] tauri::plugin::mobile::PluginInvokeError),
@@ -0,0 +1,333 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Follows a growing file like `tail -F`: the lines appended to it are streamed
+//! until the resource is closed, surviving truncation and rotation.
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    path::BaseDirectory,
+    Manager, Resource, ResourceId, Runtime, Webview,
+};
+
+use std::{
+    fs::File,
+    io::{ErrorKind, Read, Result, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{sleep, spawn},
+    time::Duration,
+};
+
+use crate::{
+    commands::{resolve_path, CommandResult},
+    scope::Entry,
+    SafeFilePath,
+};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Shorter intervals would keep a thread busy polling the file.
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Size of the chunks read from the file.
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+/// Longer lines are sent in several pieces.
+const MAX_LINE_LENGTH: usize = 1024 * 1024;
+
+pub struct FollowResource(Arc<AtomicBool>);
+
+impl Resource for FollowResource {
+    fn close(self: Arc<Self>) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowOptions {
+    base_dir: Option<BaseDirectory>,
+    /// Whether the current lines of the file are sent before the appended ones
+    #[serde(default)]
+    from_start: bool,
+    poll_interval_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data")]
+pub enum FollowEvent {
+    Lines(Vec<String>),
+    /// The file was truncated, its lines are read from the start again
+    Truncated,
+    /// The file was replaced, e.g. by a log rotation, the lines of the new file are read from its start
+    Rotated,
+    /// The file could not be read, it is followed again once readable
+    Error {
+        message: String,
+    },
+}
+
+/// Whether `file` is the file at `path`, i.e. it was not replaced.
+fn same_file(file: &File, path: &Path) -> Result<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let (a, b) = (file.metadata()?, std::fs::metadata(path)?);
+        Ok(a.dev() == b.dev() && a.ino() == b.ino())
+    }
+    // the creation time can't be compared, NTFS gives it back to a file created again with the same name
+    #[cfg(windows)]
+    {
+        Ok(file_id(file)? == file_id(&File::open(path)?)?)
+    }
+}
+
+/// The volume serial number and the file index of `file`, which identify it on Windows.
+#[cfg(windows)]
+fn file_id(file: &File) -> Result<(u32, u32, u32)> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION,
+    };
+
+    // SAFETY: the structure only contains integers
+    let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+    // SAFETY: the handle is open while `file` is borrowed
+    if unsafe { GetFileInformationByHandle(file.as_raw_handle(), &mut info) } == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok((
+        info.dwVolumeSerialNumber,
+        info.nFileIndexHigh,
+        info.nFileIndexLow,
+    ))
+}
+
+struct Follower {
+    path: PathBuf,
+    file: File,
+    position: u64,
+    /// Bytes of the line being written
+    partial: Vec<u8>,
+}
+
+impl Follower {
+    fn new(path: PathBuf, from_start: bool) -> Result<Self> {
+        let mut file = File::open(&path)?;
+        let position = if from_start {
+            0
+        } else {
+            file.seek(SeekFrom::End(0))?
+        };
+        Ok(Self {
+            path,
+            file,
+            position,
+            partial: Vec::new(),
+        })
+    }
+
+    /// Reads the appended complete lines, sending them in one [`FollowEvent::Lines`] event per chunk.
+    fn read_lines(&mut self, on_event: &mut impl FnMut(FollowEvent)) -> Result<()> {
+        let mut chunk = vec![0; READ_CHUNK_SIZE];
+        loop {
+            let read = self.file.read(&mut chunk)?;
+            if read == 0 {
+                return Ok(());
+            }
+            self.position += read as u64;
+
+            let mut lines = Vec::new();
+            let mut chunk = &chunk[..read];
+            while let Some(end) = chunk.iter().position(|b| *b == b'\n') {
+                self.partial.extend_from_slice(&chunk[..end]);
+                lines.push(self.take_partial());
+                chunk = &chunk[end + 1..];
+            }
+            self.partial.extend_from_slice(chunk);
+            // a line that never ends is sent in pieces rather than buffered without limit
+            if self.partial.len() >= MAX_LINE_LENGTH {
+                lines.push(self.take_partial());
+            }
+            if !lines.is_empty() {
+                on_event(FollowEvent::Lines(lines));
+            }
+        }
+    }
+
+    fn take_partial(&mut self) -> String {
+        let mut line = std::mem::take(&mut self.partial);
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        String::from_utf8_lossy(&line).into_owned()
+    }
+
+    /// Reads what was appended since the last call, and checks whether the file was truncated or replaced
+    /// once its end is reached.
+    fn poll(&mut self, on_event: &mut impl FnMut(FollowEvent)) -> Result<()> {
+        self.read_lines(on_event)?;
+
+        let (current, replaced) = match std::fs::metadata(&self.path)
+            .and_then(|metadata| Ok((metadata, !same_file(&self.file, &self.path)?)))
+        {
+            Ok(current) => current,
+            // rotated but not created again yet
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        if replaced {
+            // the rest of the old file was read above, its last line may miss its line ending
+            if !self.partial.is_empty() {
+                on_event(FollowEvent::Lines(vec![self.take_partial()]));
+            }
+            self.file = File::open(&self.path)?;
+            self.position = 0;
+            on_event(FollowEvent::Rotated);
+            self.read_lines(on_event)?;
+        } else if current.len() < self.position {
+            self.file.seek(SeekFrom::Start(0))?;
+            self.position = 0;
+            self.partial.clear();
+            on_event(FollowEvent::Truncated);
+            self.read_lines(on_event)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub fn follow<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<FollowOptions>,
+    on_event: Channel<FollowEvent>,
+) -> CommandResult<ResourceId> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.base_dir,
+    )?;
+    let mut follower = Follower::new(resolved_path.clone(), options.from_start).map_err(|e| {
+        format!(
+            "failed to open file at path: {} with error: {e}",
+            resolved_path.display()
+        )
+    })?;
+    let interval = options
+        .poll_interval_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_POLL_INTERVAL)
+        .max(MIN_POLL_INTERVAL);
+
+    let closed = Arc::new(AtomicBool::new(false));
+    let rid = webview
+        .resources_table()
+        .add(FollowResource(closed.clone()));
+
+    spawn(move || {
+        let mut last_error = None;
+        while !closed.load(Ordering::Relaxed) {
+            let result = follower.poll(&mut |event| {
+                // the webview is gone
+                if on_event.send(event).is_err() {
+                    closed.store(true, Ordering::Relaxed);
+                }
+            });
+            match result {
+                Ok(()) => last_error = None,
+                Err(e) => {
+                    let message = format!(
+                        "failed to follow file at path: {} with error: {e}",
+                        follower.path.display()
+                    );
+                    // the same error is reported once until the file is readable again
+                    if last_error.as_ref() != Some(&message) {
+                        let _ = on_event.send(FollowEvent::Error {
+                            message: message.clone(),
+                        });
+                        last_error = Some(message);
+                    }
+                }
+            }
+            sleep(interval);
+        }
+    });
+
+    Ok(rid)
+}
+
+mod test {
+    #[test]
+    fn follower_handles_truncation_and_rotation() {
+        use super::{FollowEvent, Follower, MAX_LINE_LENGTH};
+        use std::io::Write;
+
+        let lines = |follower: &mut Follower| {
+            let mut events = Vec::new();
+            follower.poll(&mut |event| events.push(event)).unwrap();
+            events
+                .into_iter()
+                .map(|event| match event {
+                    FollowEvent::Lines(lines) => lines.join("|"),
+                    FollowEvent::Truncated => "<truncated>".into(),
+                    FollowEvent::Rotated => "<rotated>".into(),
+                    FollowEvent::Error { message } => message,
+                })
+                .collect::<Vec<_>>()
+        };
+        let append = |path: &std::path::Path, data: &str| {
+            std::fs::OpenOptions::new()
+                .append(true)
+                .open(path)
+                .unwrap()
+                .write_all(data.as_bytes())
+                .unwrap();
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let path = dir.join("app.log");
+        std::fs::write(&path, "old\n").unwrap();
+
+        let mut follower = Follower::new(path.clone(), false).unwrap();
+        assert!(lines(&mut follower).is_empty());
+
+        append(&path, "one\r\ntw");
+        assert_eq!(lines(&mut follower), ["one"]);
+        append(&path, "o\nthree\n");
+        assert_eq!(lines(&mut follower), ["two|three"]);
+
+        std::fs::write(&path, "new\n").unwrap();
+        assert_eq!(lines(&mut follower), ["<truncated>", "new"]);
+
+        append(&path, "last");
+        std::fs::rename(&path, dir.join("app.log.1")).unwrap();
+        assert!(lines(&mut follower).is_empty());
+        std::fs::write(&path, "rotated\n").unwrap();
+        assert_eq!(lines(&mut follower), ["last", "<rotated>", "rotated"]);
+
+        // large appends are sent in several events
+        let line = "x".repeat(1023);
+        append(&path, &format!("{line}\n").repeat(200));
+        let events = lines(&mut follower);
+        assert!(events.len() > 1);
+        assert_eq!(events.concat().matches('x').count(), 200 * 1023);
+
+        // a line without line ending is sent once it reaches the maximum length
+        append(&path, &"y".repeat(MAX_LINE_LENGTH + 10));
+        assert_eq!(lines(&mut follower), ["y".repeat(MAX_LINE_LENGTH)]);
+        append(&path, "\n");
+        assert_eq!(lines(&mut follower), ["y".repeat(10)]);
+    }
+}
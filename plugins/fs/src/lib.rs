@@ -21,17 +21,27 @@ use tauri::{
     AppHandle, DragDropEvent, Manager, RunEvent, Runtime, WindowEvent,
 };
 
+#[cfg(feature = "archive")]
+mod archive;
+mod atomic;
 mod commands;
 mod config;
 #[cfg(not(target_os = "android"))]
 mod desktop;
+mod encoding;
 mod error;
 mod file_path;
+mod follow;
 #[cfg(target_os = "android")]
 mod mobile;
 #[cfg(target_os = "android")]
 mod models;
 mod scope;
+mod temp;
+#[cfg(feature = "trash")]
+mod trash;
+mod volume;
+mod walk;
 #[cfg(feature = "watch")]
 mod watcher;
 
@@ -343,6 +353,73 @@ impl<R: Runtime> Fs<R> {
         .read_to_end(&mut buf)?;
         Ok(buf)
     }
+
+    /// Replaces the contents of the file at `path` atomically: `contents` is written to
+    /// a temporary file next to it which is then renamed over it, keeping its permissions.
+    ///
+    /// When `sync` is `true`, the file and its directory are flushed to the disk before returning.
+    pub fn write_atomic<P: Into<FilePath>, C: AsRef<[u8]>>(
+        &self,
+        path: P,
+        contents: C,
+        sync: bool,
+    ) -> std::io::Result<()> {
+        let path = io_path(path)?;
+        atomic::write(
+            &path,
+            contents.as_ref(),
+            atomic::AtomicWriteOptions {
+                sync,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Creates a symbolic link at `link` pointing to `target`.
+    ///
+    /// A relative `target` is resolved from the directory of the link.
+    pub fn symlink<P: Into<FilePath>, Q: Into<FilePath>>(
+        &self,
+        target: P,
+        link: Q,
+    ) -> std::io::Result<()> {
+        let target = io_path(target)?;
+        let link = io_path(link)?;
+        let source = commands::resolve_link_target(&link, &target)?;
+        commands::create_symlink(&target, &source, &link)
+    }
+
+    /// Creates a hard link at `link` pointing to `target`.
+    pub fn link<P: Into<FilePath>, Q: Into<FilePath>>(
+        &self,
+        target: P,
+        link: Q,
+    ) -> std::io::Result<()> {
+        std::fs::hard_link(io_path(target)?, io_path(link)?)
+    }
+
+    /// Returns the target of the symbolic link at `path`.
+    pub fn read_link<P: Into<FilePath>>(&self, path: P) -> std::io::Result<std::path::PathBuf> {
+        std::fs::read_link(io_path(path)?)
+    }
+
+    /// Sets the permission bits of `path`.
+    ///
+    /// On Windows only the owner write bit is used, to toggle the read-only attribute.
+    pub fn chmod<P: Into<FilePath>>(&self, path: P, mode: u32) -> std::io::Result<()> {
+        commands::set_mode(&io_path(path)?, mode)
+    }
+
+    /// Sets the access and modification times of `path`, in milliseconds since the Unix epoch.
+    pub fn utime<P: Into<FilePath>>(&self, path: P, atime: i64, mtime: i64) -> std::io::Result<()> {
+        commands::set_times(&io_path(path)?, atime, mtime)
+    }
+}
+
+fn io_path<P: Into<FilePath>>(path: P) -> std::io::Result<std::path::PathBuf> {
+    path.into()
+        .into_path()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))
 }
 
 // implement ScopeObject here instead of in the scope module because it is also used on the build script
@@ -395,12 +472,16 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
             commands::create,
             commands::open,
             commands::copy_file,
+            commands::copy_dir,
             commands::close,
             commands::mkdir,
             commands::read_dir,
             commands::read,
             commands::read_file,
             commands::read_text_file,
+            commands::hash,
+            commands::fhash,
+            commands::cancel_hash,
             commands::read_text_file_lines,
             commands::read_text_file_lines_next,
             commands::remove,
@@ -409,12 +490,36 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
             commands::stat,
             commands::lstat,
             commands::fstat,
+            commands::symlink,
+            commands::link,
+            commands::read_link,
+            commands::chmod,
+            commands::utime,
+            commands::volume_info,
             commands::truncate,
             commands::ftruncate,
+            commands::lock,
+            commands::try_lock,
+            commands::unlock,
             commands::write,
             commands::write_file,
             commands::write_text_file,
             commands::exists,
+            walk::walk,
+            walk::cancel_walk,
+            follow::follow,
+            temp::create_temp_file,
+            temp::create_temp_dir,
+            #[cfg(feature = "archive")]
+            archive::extract_archive,
+            #[cfg(feature = "archive")]
+            archive::create_archive,
+            #[cfg(feature = "trash")]
+            trash::trash,
+            #[cfg(feature = "trash")]
+            trash::list_trash,
+            #[cfg(feature = "trash")]
+            trash::restore_trash,
             #[cfg(feature = "watch")]
             watcher::watch,
             #[cfg(feature = "watch")]
@@ -436,15 +541,15 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
             app.manage(Fs(app.clone()));
 
             app.manage(scope);
+            app.manage(temp::TempPaths::default());
             Ok(())
         })
-        .on_event(|app, event| {
-            if let RunEvent::WindowEvent {
+        .on_event(|app, event| match event {
+            RunEvent::WindowEvent {
                 label: _,
                 event: WindowEvent::DragDrop(DragDropEvent::Drop { paths, position: _ }),
                 ..
-            } = event
-            {
+            } => {
                 let scope = app.fs_scope();
                 for path in paths {
                     if path.is_file() {
@@ -454,6 +559,8 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
                     }
                 }
             }
+            RunEvent::Exit => app.state::<temp::TempPaths>().remove_all(app.fs_scope()),
+            _ => {}
         })
         .build()
 }
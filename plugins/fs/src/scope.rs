@@ -30,11 +30,14 @@ type EventListener = Box<dyn Fn(&Event) + Send>;
 
 /// Scope change event.
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum Event {
     /// A path has been allowed.
     PathAllowed(PathBuf),
     /// A path has been forbidden.
     PathForbidden(PathBuf),
+    /// A previously allowed path has been removed from the scope.
+    PathRevoked(PathBuf),
 }
 
 #[derive(Default)]
@@ -100,6 +103,17 @@ impl Scope {
         self.emit(Event::PathForbidden(path.to_path_buf()));
     }
 
+    /// Removes a file or directory allowed with [`Self::allow_file`] or [`Self::allow_directory`].
+    pub(crate) fn revoke(&self, path: &Path) {
+        let patterns = [path.to_path_buf(), path.join("*"), path.join("**")];
+        self.allowed
+            .lock()
+            .unwrap()
+            .retain(|allowed| !patterns.contains(allowed));
+
+        self.emit(Event::PathRevoked(path.to_path_buf()));
+    }
+
     /// List of allowed paths.
     pub fn allowed(&self) -> Vec<PathBuf> {
         self.allowed.lock().unwrap().clone()
@@ -0,0 +1,265 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Temporary files and directories, created in a directory of the app inside the temporary directory
+//! of the system and removed when their resource is closed or when the app exits.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Resource, ResourceId, Runtime, Webview};
+
+use std::{
+    collections::HashSet,
+    io::Result,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::{commands::CommandResult, FsExt, Scope};
+
+/// Paths of the temporary files and directories that were not removed yet.
+#[derive(Default)]
+pub(crate) struct TempPaths(Mutex<HashSet<PathBuf>>);
+
+impl TempPaths {
+    /// Removes all the temporary files and directories and revokes them from `scope`,
+    /// called when the app exits.
+    pub(crate) fn remove_all(&self, scope: &Scope) {
+        for path in self.0.lock().unwrap().drain() {
+            let _ = remove(&path);
+            scope.revoke(&path);
+        }
+    }
+}
+
+fn remove(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+pub struct TempResource<R: Runtime> {
+    app: AppHandle<R>,
+    path: PathBuf,
+}
+
+impl<R: Runtime> Resource for TempResource<R> {
+    fn close(self: Arc<Self>) {
+        if self
+            .app
+            .state::<TempPaths>()
+            .0
+            .lock()
+            .unwrap()
+            .remove(&self.path)
+        {
+            let _ = remove(&self.path);
+            self.app.fs_scope().revoke(&self.path);
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TempOptions {
+    /// Start of the file name, before its random part
+    prefix: Option<String>,
+    /// End of the file name, after its random part, e.g. an extension
+    suffix: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TempPath {
+    rid: ResourceId,
+    path: PathBuf,
+}
+
+/// Returns an unused path in `dir`, named after `options`.
+fn temp_path(dir: &Path, options: &TempOptions) -> Result<PathBuf> {
+    let name = format!(
+        "{}{}{}",
+        options.prefix.as_deref().unwrap_or(".tmp"),
+        uuid::Uuid::new_v4().simple(),
+        options.suffix.as_deref().unwrap_or_default()
+    );
+    // the name must not escape `dir`
+    if Path::new(&name).components().count() != 1 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid temporary file name: {name}"),
+        ));
+    }
+    Ok(dir.join(name))
+}
+
+/// Creates the directory of the app in the temporary directory of the system, where other users can
+/// create files too: the directory is only accessible to its owner, and rejected if created by someone else.
+fn create_app_dir(dir: &Path) -> Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(dir)?;
+
+    let metadata = std::fs::symlink_metadata(dir)?;
+    #[cfg(unix)]
+    let owned = {
+        use std::os::unix::fs::MetadataExt;
+        // SAFETY: `geteuid` is always successful
+        metadata.uid() == unsafe { libc::geteuid() }
+    };
+    #[cfg(not(unix))]
+    let owned = true;
+    if !metadata.is_dir() || !owned {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!(
+                "{} is not a directory owned by the current user",
+                dir.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn create_temp<R: Runtime>(
+    webview: &Webview<R>,
+    options: Option<TempOptions>,
+    is_dir: bool,
+) -> CommandResult<TempPath> {
+    let options = options.unwrap_or_default();
+    let dir = webview
+        .path()
+        .temp_dir()?
+        .join(&webview.config().identifier);
+    create_app_dir(&dir).map_err(|e| {
+        format!(
+            "failed to create directory at path: {} with error: {e}",
+            dir.display()
+        )
+    })?;
+
+    let path = temp_path(&dir, &options)?;
+    let created = if is_dir {
+        std::fs::create_dir(&path)
+    } else {
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map(drop)
+    };
+    created.map_err(|e| {
+        format!(
+            "failed to create temporary path: {} with error: {e}",
+            path.display()
+        )
+    })?;
+
+    let app = webview.app_handle().clone();
+    app.state::<TempPaths>()
+        .0
+        .lock()
+        .unwrap()
+        .insert(path.clone());
+    if is_dir {
+        app.fs_scope().allow_directory(&path, true);
+    } else {
+        app.fs_scope().allow_file(&path);
+    }
+
+    let rid = webview.resources_table().add(TempResource {
+        app,
+        path: path.clone(),
+    });
+    Ok(TempPath { rid, path })
+}
+
+#[tauri::command]
+pub fn create_temp_file<R: Runtime>(
+    webview: Webview<R>,
+    options: Option<TempOptions>,
+) -> CommandResult<TempPath> {
+    create_temp(&webview, options, false)
+}
+
+#[tauri::command]
+pub fn create_temp_dir<R: Runtime>(
+    webview: Webview<R>,
+    options: Option<TempOptions>,
+) -> CommandResult<TempPath> {
+    create_temp(&webview, options, true)
+}
+
+mod test {
+    #[test]
+    fn temp_paths_are_unique_and_removed() {
+        use super::{temp_path, Scope, TempOptions, TempPaths};
+
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let options = TempOptions {
+            prefix: Some("report-".into()),
+            suffix: Some(".csv".into()),
+        };
+        let file = temp_path(dir, &options).unwrap();
+        let name = file.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("report-") && name.ends_with(".csv"));
+        assert_ne!(file, temp_path(dir, &options).unwrap());
+        assert!(temp_path(
+            dir,
+            &TempOptions {
+                prefix: Some("../".into()),
+                suffix: None
+            }
+        )
+        .is_err());
+
+        std::fs::write(&file, "data").unwrap();
+        let subdir = temp_path(dir, &TempOptions::default()).unwrap();
+        std::fs::create_dir_all(subdir.join("nested")).unwrap();
+
+        let scope = Scope::default();
+        scope.allow_file(&file);
+        scope.allow_directory(&subdir, true);
+        let paths = TempPaths::default();
+        paths
+            .0
+            .lock()
+            .unwrap()
+            .extend([file.clone(), subdir.clone()]);
+        paths.remove_all(&scope);
+        assert!(!file.exists() && !subdir.exists());
+        assert!(paths.0.lock().unwrap().is_empty());
+        assert!(scope.allowed().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn app_dir_is_private() {
+        use super::create_app_dir;
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+
+        let dir = root.join("com.tauri.app");
+        create_app_dir(&dir).unwrap();
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+        // an existing directory is reused
+        create_app_dir(&dir).unwrap();
+
+        // a link planted by another user is rejected
+        let link = root.join("com.tauri.link");
+        std::os::unix::fs::symlink(&dir, &link).unwrap();
+        assert!(create_app_dir(&link).is_err());
+    }
+}
@@ -0,0 +1,539 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Moves files to the trash instead of deleting them.
+//!
+//! On Linux and the BSDs this implements the [freedesktop.org trash specification](https://specifications.freedesktop.org/trash-spec/latest/),
+//! which also lets the trashed items be listed and restored.
+//! macOS and Windows move the files to the system trash.
+
+use serde::Serialize;
+use tauri::{
+    ipc::{CommandScope, GlobalScope},
+    Runtime, Webview,
+};
+
+use std::path::PathBuf;
+
+use crate::{
+    commands::{resolve_path, BaseOptions, CommandResult},
+    scope::Entry,
+    SafeFilePath,
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashItem {
+    /// Location of the item in the trash, used to restore it
+    id: PathBuf,
+    name: String,
+    original_path: PathBuf,
+    /// Milliseconds since the Unix epoch
+    deleted_at: Option<i64>,
+    is_directory: bool,
+}
+
+#[tauri::command]
+pub fn trash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<()> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    {
+        freedesktop::trash(&resolved_path, &freedesktop::home_trash()?)
+            .map_err(|e| {
+                format!(
+                    "failed to move path: {} to the trash with error: {e}",
+                    resolved_path.display()
+                )
+            })
+            .map_err(Into::into)
+    }
+    #[cfg(any(target_os = "macos", windows))]
+    {
+        ::trash::delete(&resolved_path)
+            .map_err(crate::Error::from)
+            .map_err(Into::into)
+    }
+    #[cfg(any(target_os = "ios", target_os = "android"))]
+    {
+        let _ = resolved_path;
+        Err(crate::Error::TrashUnsupported("moving to the trash").into())
+    }
+}
+
+/// Lists the items of the trash whose original path is in the scope.
+#[tauri::command]
+pub fn list_trash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+) -> CommandResult<Vec<TrashItem>> {
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    {
+        let scope = crate::commands::fs_scope(&webview, &global_scope, &command_scope)?;
+        let trash_dirs = freedesktop::trash_dirs(freedesktop::home_trash()?);
+        let items = freedesktop::list(&trash_dirs)
+            .map_err(|e| format!("failed to list the trash with error: {e}"))?;
+        Ok(items
+            .into_iter()
+            .filter(|item| scope.is_allowed(&item.original_path))
+            .collect())
+    }
+    #[cfg(not(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    )))]
+    {
+        let _ = (webview, global_scope, command_scope);
+        Err(crate::Error::TrashUnsupported("listing the trash").into())
+    }
+}
+
+/// Moves a trashed item back to its original path, which must be in the scope and free.
+#[tauri::command]
+pub fn restore_trash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    id: PathBuf,
+) -> CommandResult<()> {
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    {
+        let trash_dirs = freedesktop::trash_dirs(freedesktop::home_trash()?);
+        let item = freedesktop::find(&id, &trash_dirs)?;
+        let scope = crate::commands::fs_scope(&webview, &global_scope, &command_scope)?;
+        if !scope.is_allowed(&item.original_path) {
+            return Err(crate::Error::PathForbidden(item.original_path).into());
+        }
+        freedesktop::restore(&item)
+            .map_err(|e| {
+                format!(
+                    "failed to restore path: {} from the trash with error: {e}",
+                    item.original_path.display()
+                )
+            })
+            .map_err(Into::into)
+    }
+    #[cfg(not(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    )))]
+    {
+        let _ = (webview, global_scope, command_scope, id);
+        Err(crate::Error::TrashUnsupported("restoring from the trash").into())
+    }
+}
+
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+))]
+mod freedesktop {
+    use percent_encoding::{percent_decode, percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+    use std::{
+        ffi::{OsStr, OsString},
+        fs::{self, DirBuilder, OpenOptions},
+        io::{Error, ErrorKind, Result, Write},
+        os::unix::{
+            ffi::{OsStrExt, OsStringExt},
+            fs::{DirBuilderExt, MetadataExt},
+        },
+        path::{Path, PathBuf},
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::TrashItem;
+
+    /// Characters kept as is in the `Path` key, like RFC 2396 paths
+    const PATH_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+        .remove(b'/')
+        .remove(b'-')
+        .remove(b'_')
+        .remove(b'.')
+        .remove(b'~');
+
+    pub(super) struct TrashDir {
+        pub(super) path: PathBuf,
+        /// Directory the original paths are relative to, `None` for the home trash which stores absolute paths
+        pub(super) topdir: Option<PathBuf>,
+    }
+
+    pub(super) fn home_trash() -> Result<PathBuf> {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|data_home| data_home.is_absolute())
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+            .map(|data_home| data_home.join("Trash"))
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, "could not find the home directory"))
+    }
+
+    fn create_trash_dir(path: &Path) -> Result<()> {
+        let mut builder = DirBuilder::new();
+        builder.recursive(true).mode(0o700);
+        builder.create(path.join("files"))?;
+        builder.create(path.join("info"))
+    }
+
+    fn uid() -> u32 {
+        // SAFETY: `getuid` is always successful
+        unsafe { libc::getuid() }
+    }
+
+    /// Whether `path` is a directory owned by the user, and not a symlink.
+    fn is_user_dir(path: &Path) -> bool {
+        fs::symlink_metadata(path)
+            .is_ok_and(|metadata| metadata.is_dir() && metadata.uid() == uid())
+    }
+
+    /// The trash of the mount point containing `path`, for files which are not on the home trash device.
+    fn topdir_trash(path: &Path, dev: u64) -> Result<TrashDir> {
+        let mut topdir = path;
+        while let Some(parent) = topdir.parent() {
+            if fs::metadata(parent)?.dev() != dev {
+                break;
+            }
+            topdir = parent;
+        }
+
+        // a shared `$topdir/.Trash` must be a sticky directory and not a symlink
+        let admin_trash = topdir.join(".Trash");
+        if fs::symlink_metadata(&admin_trash)
+            .is_ok_and(|metadata| metadata.is_dir() && metadata.mode() & 0o1000 != 0)
+        {
+            let path = admin_trash.join(uid().to_string());
+            // otherwise fallback to `$topdir/.Trash-$uid`
+            if create_trash_dir(&path).is_ok() && is_user_dir(&path) {
+                return Ok(TrashDir {
+                    path,
+                    topdir: Some(topdir.to_path_buf()),
+                });
+            }
+        }
+
+        let path = topdir.join(format!(".Trash-{}", uid()));
+        create_trash_dir(&path)?;
+        if !is_user_dir(&path) {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                format!("{} is not a trash directory of the user", path.display()),
+            ));
+        }
+        Ok(TrashDir {
+            path,
+            topdir: Some(topdir.to_path_buf()),
+        })
+    }
+
+    pub(super) fn trash(path: &Path, home_trash: &Path) -> Result<()> {
+        let name = path
+            .file_name()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "path has no file name"))?;
+        // symlinks are trashed themselves, only the parent directory is resolved
+        let path = match path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            Some(parent) => parent.canonicalize()?.join(name),
+            None => std::env::current_dir()?.join(name),
+        };
+        let dev = fs::symlink_metadata(&path)?.dev();
+
+        create_trash_dir(home_trash)?;
+        let trash_dir = if fs::metadata(home_trash)?.dev() == dev {
+            TrashDir {
+                path: home_trash.to_path_buf(),
+                topdir: None,
+            }
+        } else {
+            topdir_trash(&path, dev)?
+        };
+
+        let original_path = match &trash_dir.topdir {
+            Some(topdir) => path.strip_prefix(topdir).unwrap_or(&path),
+            None => &path,
+        };
+        let info = format!(
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            percent_encode(original_path.as_os_str().as_bytes(), PATH_ENCODE_SET),
+            format_deletion_date(SystemTime::now())
+        );
+
+        for i in 1.. {
+            let mut trash_name = name.to_os_string();
+            if i > 1 {
+                trash_name.push(format!(".{i}"));
+            }
+            let info_path = info_path(&trash_dir.path, &trash_name);
+            let file_path = trash_dir.path.join("files").join(&trash_name);
+
+            // the info file is created first to reserve the name
+            let mut info_file = match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&info_path)
+            {
+                Ok(file) => file,
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            };
+            // a file left without its info
+            if fs::symlink_metadata(&file_path).is_ok() {
+                drop(info_file);
+                fs::remove_file(&info_path)?;
+                continue;
+            }
+
+            let result = info_file
+                .write_all(info.as_bytes())
+                .and_then(|()| fs::rename(&path, &file_path));
+            if result.is_err() {
+                let _ = fs::remove_file(&info_path);
+            }
+            return result;
+        }
+
+        unreachable!()
+    }
+
+    fn info_path(trash_dir: &Path, name: &OsStr) -> PathBuf {
+        let mut info_name = name.to_os_string();
+        info_name.push(".trashinfo");
+        trash_dir.join("info").join(info_name)
+    }
+
+    /// The home trash and the trash directories of the mount points.
+    pub(super) fn trash_dirs(home_trash: PathBuf) -> Vec<TrashDir> {
+        let mut trash_dirs = vec![TrashDir {
+            path: home_trash,
+            topdir: None,
+        }];
+
+        #[cfg(target_os = "linux")]
+        for topdir in crate::volume::mount_points() {
+            for path in [
+                topdir.join(".Trash").join(uid().to_string()),
+                topdir.join(format!(".Trash-{}", uid())),
+            ] {
+                if path.join("info").is_dir() {
+                    trash_dirs.push(TrashDir {
+                        path,
+                        topdir: Some(topdir.clone()),
+                    });
+                }
+            }
+        }
+
+        trash_dirs
+    }
+
+    fn read_item(trash_dir: &TrashDir, name: &OsStr) -> Result<TrashItem> {
+        let info = fs::read_to_string(info_path(&trash_dir.path, name))?;
+        let mut original_path = None;
+        let mut deleted_at = None;
+        for line in info
+            .lines()
+            .skip_while(|line| line.trim() != "[Trash Info]")
+            .skip(1)
+            .take_while(|line| !line.starts_with('['))
+        {
+            if let Some(path) = line.strip_prefix("Path=") {
+                let path = OsString::from_vec(percent_decode(path.trim().as_bytes()).collect());
+                original_path = Some(match &trash_dir.topdir {
+                    Some(topdir) => topdir.join(path),
+                    None => PathBuf::from(path),
+                });
+            } else if let Some(date) = line.strip_prefix("DeletionDate=") {
+                deleted_at = parse_deletion_date(date.trim());
+            }
+        }
+        let original_path = original_path
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "trash info without path"))?;
+
+        let id = trash_dir.path.join("files").join(name);
+        Ok(TrashItem {
+            is_directory: fs::symlink_metadata(&id)?.is_dir(),
+            id,
+            name: original_path
+                .file_name()
+                .unwrap_or(name)
+                .to_string_lossy()
+                .into_owned(),
+            original_path,
+            deleted_at,
+        })
+    }
+
+    pub(super) fn list(trash_dirs: &[TrashDir]) -> Result<Vec<TrashItem>> {
+        let mut items = Vec::new();
+        for trash_dir in trash_dirs {
+            let entries = match fs::read_dir(trash_dir.path.join("info")) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            for entry in entries {
+                let file_name = entry?.file_name();
+                let Some(name) = file_name.as_bytes().strip_suffix(b".trashinfo") else {
+                    continue;
+                };
+                // skips the invalid info files and the ones without a trashed file
+                if let Ok(item) = read_item(trash_dir, OsStr::from_bytes(name)) {
+                    items.push(item);
+                }
+            }
+        }
+        Ok(items)
+    }
+
+    /// Finds the item stored at `id` in one of the trash directories.
+    pub(super) fn find(id: &Path, trash_dirs: &[TrashDir]) -> crate::Result<TrashItem> {
+        let not_in_trash = || crate::Error::NotInTrash(id.to_path_buf());
+        let name = id.file_name().ok_or_else(not_in_trash)?;
+        let trash_dir = trash_dirs
+            .iter()
+            .find(|trash_dir| id.parent() == Some(trash_dir.path.join("files").as_path()))
+            .ok_or_else(not_in_trash)?;
+        read_item(trash_dir, name).map_err(|_| not_in_trash())
+    }
+
+    pub(super) fn restore(item: &TrashItem) -> Result<()> {
+        if fs::symlink_metadata(&item.original_path).is_ok() {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} already exists", item.original_path.display()),
+            ));
+        }
+        if let Some(parent) = item.original_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(&item.id, &item.original_path)?;
+
+        let trash_dir = item.id.parent().and_then(Path::parent);
+        if let (Some(trash_dir), Some(name)) = (trash_dir, item.id.file_name()) {
+            fs::remove_file(info_path(trash_dir, name))?;
+        }
+        Ok(())
+    }
+
+    /// Formats `time` as `YYYY-MM-DDThh:mm:ss` in the local time zone.
+    fn format_deletion_date(time: SystemTime) -> String {
+        let secs = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as libc::time_t);
+        // SAFETY: `localtime_r` only writes to `tm`, which is valid when zeroed
+        let tm = unsafe {
+            let mut tm = std::mem::zeroed::<libc::tm>();
+            libc::localtime_r(&secs, &mut tm);
+            tm
+        };
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            tm.tm_year + 1900,
+            tm.tm_mon + 1,
+            tm.tm_mday,
+            tm.tm_hour,
+            tm.tm_min,
+            tm.tm_sec
+        )
+    }
+
+    /// Parses a local `YYYY-MM-DDThh:mm:ss` date into milliseconds since the Unix epoch.
+    fn parse_deletion_date(date: &str) -> Option<i64> {
+        let (date, time) = date.split_once('T')?;
+        let mut date = date.splitn(3, '-').map(str::parse::<i32>);
+        let mut time = time.splitn(3, ':').map(str::parse::<i32>);
+        // SAFETY: a zeroed `tm` is valid and `mktime` only reads and normalizes it
+        let secs = unsafe {
+            let mut tm = std::mem::zeroed::<libc::tm>();
+            tm.tm_year = date.next()?.ok()? - 1900;
+            tm.tm_mon = date.next()?.ok()? - 1;
+            tm.tm_mday = date.next()?.ok()?;
+            tm.tm_hour = time.next()?.ok()?;
+            tm.tm_min = time.next()?.ok()?;
+            tm.tm_sec = time.next()?.ok()?;
+            // lets `mktime` find out whether daylight saving time applies
+            tm.tm_isdst = -1;
+            libc::mktime(&mut tm)
+        };
+        (secs != -1).then_some(secs as i64 * 1000)
+    }
+}
+
+mod test {
+    #[test]
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    fn trash_list_and_restore() {
+        use super::freedesktop::{find, list, restore, trash, TrashDir};
+        use std::{
+            fs,
+            io::ErrorKind,
+            time::{SystemTime, UNIX_EPOCH},
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        let home_trash = dir.join("Trash");
+        let trash_dirs = [TrashDir {
+            path: home_trash.clone(),
+            topdir: None,
+        }];
+
+        let file = dir.join("docs/report 1%.txt");
+        let original_path = dir.canonicalize().unwrap().join("docs/report 1%.txt");
+        fs::write(&file, "v1").unwrap();
+        trash(&file, &home_trash).unwrap();
+        fs::write(&file, "v2").unwrap();
+        trash(&file, &home_trash).unwrap();
+        assert!(!file.exists());
+
+        let info = fs::read_to_string(home_trash.join("info/report 1%.txt.trashinfo")).unwrap();
+        assert!(info.contains("/docs/report%201%25.txt\n"));
+
+        let mut items = list(&trash_dirs).unwrap();
+        items.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].id, home_trash.join("files/report 1%.txt.2"));
+        assert_eq!(items[1].name, "report 1%.txt");
+        assert_eq!(items[1].original_path, original_path);
+        let deleted_at = items[1].deleted_at.unwrap();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        assert!((now - deleted_at).abs() < 60_000);
+
+        let item = find(&items[1].id, &trash_dirs).unwrap();
+        restore(&item).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "v2");
+        assert_eq!(list(&trash_dirs).unwrap().len(), 1);
+
+        // the original path is taken
+        let item = find(&items[0].id, &trash_dirs).unwrap();
+        assert_eq!(restore(&item).unwrap_err().kind(), ErrorKind::AlreadyExists);
+        assert!(find(&file, &trash_dirs).is_err());
+    }
+}
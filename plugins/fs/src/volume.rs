@@ -0,0 +1,210 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Space and type of the volume containing a path.
+
+use serde::Serialize;
+
+use std::{io::Result, path::Path};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeInfo {
+    total_bytes: u64,
+    free_bytes: u64,
+    /// Free bytes usable by the current user, which excludes the blocks reserved to the system
+    available_bytes: u64,
+    /// File system type, e.g. `ext4`, `apfs` or `NTFS`
+    fs_type: Option<String>,
+    readonly: bool,
+}
+
+pub(crate) fn volume_info(path: &Path) -> Result<VolumeInfo> {
+    let stats = fs4::statvfs(path)?;
+    let (fs_type, readonly) = details(path)?;
+    Ok(VolumeInfo {
+        total_bytes: stats.total_space(),
+        free_bytes: stats.free_space(),
+        available_bytes: stats.available_space(),
+        fs_type,
+        readonly,
+    })
+}
+
+#[cfg(unix)]
+fn details(path: &Path) -> Result<(Option<String>, bool)> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `c_path` is a valid C string and `stat` is only read if `statvfs` succeeds
+    if unsafe { libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let stat = unsafe { stat.assume_init() };
+    let readonly = stat.f_flag & libc::ST_RDONLY != 0;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let fs_type = std::fs::read_to_string("/proc/self/mountinfo")
+        .ok()
+        .zip(path.canonicalize().ok())
+        .and_then(|(mountinfo, path)| mount_fs_type(&mountinfo, &path));
+    #[cfg(target_vendor = "apple")]
+    let fs_type = {
+        let mut stat = std::mem::MaybeUninit::<libc::statfs>::uninit();
+        // SAFETY: same as `statvfs` above, `f_fstypename` is nul terminated
+        if unsafe { libc::statfs(c_path.as_ptr(), stat.as_mut_ptr()) } == 0 {
+            let stat = unsafe { stat.assume_init() };
+            let name = unsafe { std::ffi::CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+            Some(name.to_string_lossy().into_owned())
+        } else {
+            None
+        }
+    };
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+    let fs_type = None;
+
+    Ok((fs_type, readonly))
+}
+
+/// Finds the type of the file system mounted closest to `path` in the contents of `/proc/self/mountinfo`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn mount_fs_type(mountinfo: &str, path: &Path) -> Option<String> {
+    let mut found: Option<(usize, &str)> = None;
+    for line in mountinfo.lines() {
+        // 36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+        let Some((mount, fs)) = line.split_once(" - ") else {
+            continue;
+        };
+        let (Some(mount_point), Some(fs_type)) = (mount.split(' ').nth(4), fs.split(' ').next())
+        else {
+            continue;
+        };
+        let mount_point = unescape_mount_point(mount_point);
+        let depth = Path::new(&mount_point).components().count();
+        // later mounts on the same point hide the earlier ones
+        if path.starts_with(&mount_point) && found.map_or(true, |(d, _)| depth >= d) {
+            found = Some((depth, fs_type));
+        }
+    }
+    found.map(|(_, fs_type)| fs_type.to_string())
+}
+
+/// Mount points listed in `/proc/self/mountinfo`.
+#[cfg(all(feature = "trash", target_os = "linux"))]
+pub(crate) fn mount_points() -> Vec<std::path::PathBuf> {
+    std::fs::read_to_string("/proc/self/mountinfo")
+        .map(|mountinfo| {
+            mountinfo
+                .lines()
+                .filter_map(|line| line.split(' ').nth(4))
+                .map(|mount_point| unescape_mount_point(mount_point).into())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Mount points escape spaces, tabs, new lines and backslashes as octal sequences, e.g. `\040`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn unescape_mount_point(mount_point: &str) -> String {
+    let bytes = mount_point.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes
+            .get(i + 1..i + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        match octal {
+            Some(byte) if bytes[i] == b'\\' => {
+                unescaped.push(byte);
+                i += 4;
+            }
+            _ => {
+                unescaped.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+#[cfg(windows)]
+fn details(path: &Path) -> Result<(Option<String>, bool)> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::{
+        Foundation::MAX_PATH, Storage::FileSystem::GetVolumeInformationW,
+        Storage::FileSystem::GetVolumePathNameW, System::SystemServices::FILE_READ_ONLY_VOLUME,
+    };
+
+    let wide_path: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut root = [0u16; MAX_PATH as usize + 1];
+    // SAFETY: both buffers are nul terminated and their lengths are passed along
+    if unsafe { GetVolumePathNameW(wide_path.as_ptr(), root.as_mut_ptr(), root.len() as u32) } == 0
+    {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let mut flags = 0;
+    let mut name = [0u16; MAX_PATH as usize + 1];
+    // SAFETY: `root` is nul terminated and the optional outputs are null
+    let found = unsafe {
+        GetVolumeInformationW(
+            root.as_ptr(),
+            std::ptr::null_mut(),
+            0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut flags,
+            name.as_mut_ptr(),
+            name.len() as u32,
+        )
+    } != 0;
+    if !found {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let len = name.iter().position(|c| *c == 0).unwrap_or(name.len());
+    let fs_type = Some(String::from_utf16_lossy(&name[..len])).filter(|t| !t.is_empty());
+    Ok((fs_type, flags & FILE_READ_ONLY_VOLUME != 0))
+}
+
+mod test {
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn mount_fs_type_uses_the_closest_mount() {
+        use super::mount_fs_type;
+        use std::path::Path;
+
+        let mountinfo = "\
+22 1 8:1 / / rw,relatime shared:1 - ext4 /dev/sda1 rw
+30 22 0:25 / /mnt/my\\040share rw,relatime shared:2 - nfs4 server:/export rw
+31 22 0:26 / /mnt/my rw,relatime shared:3 - tmpfs tmpfs rw
+32 30 0:27 / /mnt/my\\040share/fuse rw,relatime shared:4 - fuse.sshfs host: rw
+";
+        let fs_type = |path: &str| mount_fs_type(mountinfo, Path::new(path));
+
+        assert_eq!(fs_type("/home/user").as_deref(), Some("ext4"));
+        assert_eq!(fs_type("/mnt/my share/data").as_deref(), Some("nfs4"));
+        assert_eq!(fs_type("/mnt/my/data").as_deref(), Some("tmpfs"));
+        assert_eq!(
+            fs_type("/mnt/my share/fuse/x").as_deref(),
+            Some("fuse.sshfs")
+        );
+        // `/mnt/myfile` is not under `/mnt/my`
+        assert_eq!(fs_type("/mnt/myfile").as_deref(), Some("ext4"));
+    }
+
+    #[test]
+    fn volume_info_of_the_temp_dir() {
+        let info = super::volume_info(&std::env::temp_dir()).unwrap();
+        assert!(info.total_bytes > 0);
+        assert!(info.free_bytes >= info.available_bytes);
+        assert!(!info.readonly);
+    }
+}
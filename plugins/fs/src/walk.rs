@@ -0,0 +1,320 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    path::BaseDirectory,
+    Manager, Resource, ResourceId, Runtime, Webview,
+};
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::spawn,
+};
+
+use crate::{
+    commands::{fs_scope, resolve_path, CommandResult, DirEntry},
+    scope::Entry,
+    SafeFilePath,
+};
+
+const DEFAULT_BATCH_SIZE: usize = 100;
+
+pub struct WalkResource(Arc<AtomicBool>);
+
+impl Resource for WalkResource {}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkOptions {
+    base_dir: Option<BaseDirectory>,
+    /// Only entries matching one of these globs are emitted, directories are still traversed
+    #[serde(default)]
+    include: Vec<String>,
+    /// Entries matching one of these globs are neither emitted nor traversed
+    #[serde(default)]
+    exclude: Vec<String>,
+    max_depth: Option<usize>,
+    /// Whether hidden files and directories are walked
+    #[serde(default)]
+    hidden: bool,
+    /// Whether `.gitignore` files are respected
+    #[serde(default)]
+    gitignore: bool,
+    #[serde(default)]
+    follow_symlinks: bool,
+    batch_size: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkEntry {
+    #[serde(flatten)]
+    entry: DirEntry,
+    /// Path relative to the walked directory
+    path: PathBuf,
+    depth: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data")]
+pub enum WalkEvent {
+    Entries(Vec<WalkEntry>),
+    /// An entry could not be read, the walk goes on
+    #[serde(rename_all = "camelCase")]
+    Error {
+        path: Option<PathBuf>,
+        message: String,
+    },
+    Finished {
+        cancelled: bool,
+    },
+}
+
+pub(crate) fn glob_set(patterns: &[String]) -> crate::Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(GlobBuilder::new(pattern).literal_separator(true).build()?);
+    }
+    Ok(builder.build()?)
+}
+
+fn walk_inner(
+    root: &Path,
+    options: &WalkOptions,
+    is_allowed: impl Fn(&Path) -> bool + Send + Sync + 'static,
+    cancelled: &AtomicBool,
+    mut on_event: impl FnMut(WalkEvent),
+) -> crate::Result<()> {
+    let include = glob_set(&options.include)?;
+    let exclude = glob_set(&options.exclude)?;
+
+    let walker_root = root.to_path_buf();
+    let walker = WalkBuilder::new(root)
+        .standard_filters(false)
+        .hidden(!options.hidden)
+        .git_ignore(options.gitignore)
+        .git_exclude(options.gitignore)
+        .parents(options.gitignore)
+        .require_git(false)
+        .follow_links(options.follow_symlinks)
+        .max_depth(options.max_depth)
+        .filter_entry(move |entry| {
+            let path = entry.path();
+            let excluded = path
+                .strip_prefix(&walker_root)
+                .is_ok_and(|relative| exclude.is_match(relative));
+            // entries out of the scope are silently left out
+            !excluded && is_allowed(path)
+        })
+        .build();
+
+    let batch_size = options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+    let mut batch = Vec::with_capacity(batch_size);
+    for entry in walker {
+        if cancelled.load(Ordering::Relaxed) {
+            on_event(WalkEvent::Finished { cancelled: true });
+            return Ok(());
+        }
+
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let path = match &e {
+                    ignore::Error::WithPath { path, .. } => Some(path.clone()),
+                    _ => None,
+                };
+                on_event(WalkEvent::Error {
+                    path,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        // the walked directory itself
+        if entry.depth() == 0 {
+            continue;
+        }
+
+        let Ok(path) = entry.path().strip_prefix(root).map(Path::to_path_buf) else {
+            continue;
+        };
+        if !options.include.is_empty() && !include.is_match(&path) {
+            continue;
+        }
+
+        let file_type = entry.file_type();
+        let is_symlink = entry.path_is_symlink();
+        batch.push(WalkEntry {
+            entry: DirEntry {
+                name: Some(entry.file_name().to_string_lossy().to_string()),
+                is_directory: file_type.is_some_and(|t| t.is_dir()),
+                is_file: file_type.is_some_and(|t| t.is_file()),
+                is_symlink,
+            },
+            path,
+            depth: entry.depth(),
+        });
+
+        if batch.len() >= batch_size {
+            on_event(WalkEvent::Entries(std::mem::replace(
+                &mut batch,
+                Vec::with_capacity(batch_size),
+            )));
+        }
+    }
+
+    if !batch.is_empty() {
+        on_event(WalkEvent::Entries(batch));
+    }
+    on_event(WalkEvent::Finished { cancelled: false });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn walk<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<WalkOptions>,
+    on_event: Channel<WalkEvent>,
+) -> CommandResult<ResourceId> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.base_dir,
+    )?;
+    let scope = fs_scope(&webview, &global_scope, &command_scope)?;
+
+    // fail early on invalid patterns instead of reporting them through the channel
+    glob_set(&options.include)?;
+    glob_set(&options.exclude)?;
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let rid = webview
+        .resources_table()
+        .add(WalkResource(cancelled.clone()));
+
+    spawn(move || {
+        let result = walk_inner(
+            &resolved_path,
+            &options,
+            move |path| scope.is_allowed(path),
+            &cancelled,
+            |event| {
+                let _ = on_event.send(event);
+            },
+        );
+        if let Err(e) = result {
+            let _ = on_event.send(WalkEvent::Error {
+                path: Some(resolved_path),
+                message: e.to_string(),
+            });
+            let _ = on_event.send(WalkEvent::Finished { cancelled: false });
+        }
+        let _ = webview.resources_table().close(rid);
+    });
+
+    Ok(rid)
+}
+
+#[tauri::command]
+pub fn cancel_walk<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> CommandResult<()> {
+    // the walk may have already finished
+    if let Ok(walk) = webview.resources_table().take::<WalkResource>(rid) {
+        walk.0.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+mod test {
+    #[test]
+    fn walk_filters_entries() {
+        use super::{walk_inner, WalkEvent, WalkOptions};
+        use std::{path::Path, sync::atomic::AtomicBool};
+
+        let walk_paths = |root: &Path, options: WalkOptions| {
+            let mut paths = Vec::new();
+            walk_inner(
+                root,
+                &options,
+                |_| true,
+                &AtomicBool::new(false),
+                |event| {
+                    if let WalkEvent::Entries(entries) = event {
+                        paths.extend(
+                            entries
+                                .into_iter()
+                                .map(|e| e.path.to_string_lossy().replace('\\', "/")),
+                        );
+                    }
+                },
+            )
+            .unwrap();
+            paths.sort();
+            paths
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        std::fs::create_dir_all(dir.join("src/nested")).unwrap();
+        std::fs::create_dir_all(dir.join("target")).unwrap();
+        std::fs::create_dir_all(dir.join(".cache")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "").unwrap();
+        std::fs::write(dir.join("src/nested/mod.rs"), "").unwrap();
+        std::fs::write(dir.join("src/notes.md"), "").unwrap();
+        std::fs::write(dir.join("target/out.rs"), "").unwrap();
+        std::fs::write(dir.join(".cache/data.rs"), "").unwrap();
+        std::fs::write(dir.join(".gitignore"), "target\n").unwrap();
+
+        assert_eq!(
+            walk_paths(
+                dir,
+                WalkOptions {
+                    include: vec!["**/*.rs".into()],
+                    gitignore: true,
+                    ..Default::default()
+                }
+            ),
+            ["src/lib.rs", "src/nested/mod.rs"]
+        );
+
+        assert_eq!(
+            walk_paths(
+                dir,
+                WalkOptions {
+                    include: vec!["**/*.rs".into()],
+                    exclude: vec!["src".into()],
+                    hidden: true,
+                    ..Default::default()
+                }
+            ),
+            [".cache/data.rs", "target/out.rs"]
+        );
+
+        assert_eq!(
+            walk_paths(
+                dir,
+                WalkOptions {
+                    max_depth: Some(1),
+                    ..Default::default()
+                }
+            ),
+            ["src", "target"]
+        );
+    }
+}
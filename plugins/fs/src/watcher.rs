@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
-use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, FileIdMap};
-use serde::Deserialize;
+use globset::GlobSet;
+use notify::{
+    event::{ModifyKind, RenameMode},
+    Config, Event, EventKind, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher,
+};
+use notify_debouncer_full::{new_debouncer_opt, DebounceEventResult, Debouncer, FileIdMap};
+use serde::{Deserialize, Serialize};
 use tauri::{
     ipc::{Channel, CommandScope, GlobalScope},
     path::BaseDirectory,
@@ -12,9 +16,9 @@ use tauri::{
 };
 
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
-        mpsc::{channel, Receiver},
+        mpsc::{channel, Receiver, RecvTimeoutError},
         Mutex,
     },
     thread::spawn,
@@ -24,6 +28,7 @@ use std::{
 use crate::{
     commands::{resolve_path, CommandResult},
     scope::Entry,
+    walk::glob_set,
     SafeFilePath,
 };
 
@@ -48,37 +53,306 @@ impl Resource for WatcherResource {}
 
 enum WatcherKind {
     Debouncer(Debouncer<RecommendedWatcher, FileIdMap>),
+    PollDebouncer(Debouncer<PollWatcher, FileIdMap>),
     Watcher(RecommendedWatcher),
+    Poll(PollWatcher),
+}
+
+impl WatcherKind {
+    fn watcher(&mut self) -> &mut dyn Watcher {
+        match self {
+            Self::Debouncer(debouncer) => debouncer.watcher(),
+            Self::PollDebouncer(debouncer) => debouncer.watcher(),
+            Self::Watcher(watcher) => watcher,
+            Self::Poll(watcher) => watcher,
+        }
+    }
+}
+
+enum EventReceiver {
+    Debounced(Receiver<DebounceEventResult>),
+    Raw(Receiver<notify::Result<Event>>),
+}
+
+/// Watches `paths` with a watcher of type `T`, debounced when `delay` is set.
+fn start_watcher<T: Watcher>(
+    paths: &[PathBuf],
+    recursive_mode: RecursiveMode,
+    delay: Option<Duration>,
+    config: Config,
+    debounced: fn(Debouncer<T, FileIdMap>) -> WatcherKind,
+    raw: fn(T) -> WatcherKind,
+) -> notify::Result<(WatcherKind, EventReceiver)> {
+    if let Some(delay) = delay {
+        let (tx, rx) = channel();
+        let mut debouncer =
+            new_debouncer_opt::<_, T, _>(delay, None, tx, FileIdMap::new(), config)?;
+        for path in paths {
+            debouncer.watcher().watch(path, recursive_mode)?;
+            debouncer.cache().add_root(path, recursive_mode);
+        }
+        Ok((debounced(debouncer), EventReceiver::Debounced(rx)))
+    } else {
+        let (tx, rx) = channel();
+        let mut watcher = T::new(tx, config)?;
+        for path in paths {
+            watcher.watch(path, recursive_mode)?;
+        }
+        Ok((raw(watcher), EventReceiver::Raw(rx)))
+    }
+}
+
+/// Whether `path` is on a network file system, where native events are never fired.
+#[cfg(target_os = "linux")]
+fn is_remote_fs(path: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+
+    const REMOTE_FS_MAGICS: &[u32] = &[
+        0x6969,     // NFS
+        0x517b,     // SMB
+        0xff534d42, // CIFS
+        0xfe534d42, // SMB2
+        0x65735546, // FUSE, e.g. sshfs
+        0x01021997, // 9P, e.g. WSL and container bind mounts
+        0x73757245, // Coda
+        0x5346414f, // AFS
+        0x00c36400, // Ceph
+    ];
+
+    let Ok(path) = std::ffi::CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mut stat = std::mem::MaybeUninit::<libc::statfs>::uninit();
+    // SAFETY: `path` is a valid C string and `stat` is only read if `statfs` succeeds
+    if unsafe { libc::statfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return false;
+    }
+    let stat = unsafe { stat.assume_init() };
+    // the type of `f_type` depends on the architecture, the magic numbers fit in 32 bits
+    #[allow(clippy::unnecessary_cast)]
+    REMOTE_FS_MAGICS.contains(&(stat.f_type as u32))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_remote_fs(_path: &Path) -> bool {
+    false
+}
+
+/// How long a rename source waits for its destination before being sent on its own.
+const RENAME_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Payload of the watch channel.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum WatchEvent {
+    Event(Event),
+    Error { error: String, paths: Vec<PathBuf> },
+}
+
+impl From<notify::Error> for WatchEvent {
+    fn from(error: notify::Error) -> Self {
+        Self::Error {
+            error: error.to_string(),
+            paths: error.paths.clone(),
+        }
+    }
+}
+
+/// Drops the events of ignored paths.
+struct EventFilter {
+    roots: Vec<PathBuf>,
+    ignore: GlobSet,
+}
+
+impl EventFilter {
+    fn new(roots: &[PathBuf], ignore: GlobSet) -> Self {
+        // some backends report the canonical paths, e.g. `/private/var` instead of `/var` on macOS
+        let mut filter_roots = Vec::new();
+        for root in roots {
+            filter_roots.push(root.clone());
+            if let Ok(canonical) = dunce::canonicalize(root) {
+                if &canonical != root {
+                    filter_roots.push(canonical);
+                }
+            }
+        }
+        Self {
+            roots: filter_roots,
+            ignore,
+        }
+    }
+
+    fn is_ignored(&self, path: &Path) -> bool {
+        self.roots
+            .iter()
+            .filter_map(|root| path.strip_prefix(root).ok())
+            .any(|relative| self.ignore.is_match(relative))
+    }
+
+    /// Whether the event should be sent, i.e. it concerns at least one path that is not ignored.
+    fn accepts(&self, event: &Event) -> bool {
+        self.ignore.is_empty()
+            || event.paths.is_empty()
+            || event.paths.iter().any(|path| !self.is_ignored(path))
+    }
+}
+
+/// Pairs the source and destination events of a rename into a single [`RenameMode::Both`] event.
+#[derive(Default)]
+struct RenamePairer {
+    /// A rename source waiting for its destination
+    pending: Option<Event>,
+    /// Paths of the last paired rename, some backends report it again as a [`RenameMode::Both`] event
+    last_pair: Option<Vec<PathBuf>>,
 }
 
-fn watch_raw(on_event: Channel<Event>, rx: Receiver<notify::Result<Event>>) {
+impl RenamePairer {
+    /// Returns the events ready to be sent.
+    fn push(&mut self, event: Event) -> Vec<Event> {
+        let mut ready: Vec<Event> = Vec::new();
+        match event.kind {
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                ready.extend(self.pending.replace(event));
+                self.last_pair = None;
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => match self.pending.take() {
+                Some(from) if from.attrs.tracker() == event.attrs.tracker() => {
+                    let mut paths = from.paths;
+                    paths.extend(event.paths);
+                    self.last_pair.replace(paths.clone());
+
+                    let mut both =
+                        Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)));
+                    both.paths = paths;
+                    if let Some(tracker) = event.attrs.tracker() {
+                        both = both.set_tracker(tracker);
+                    }
+                    ready.push(both);
+                }
+                pending => {
+                    ready.extend(pending);
+                    ready.push(event);
+                }
+            },
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                ready.extend(self.pending.take());
+                if self.last_pair.take().as_ref() != Some(&event.paths) {
+                    ready.push(event);
+                }
+            }
+            _ => {
+                ready.extend(self.pending.take());
+                self.last_pair = None;
+                ready.push(event);
+            }
+        }
+        ready
+    }
+
+    fn flush(&mut self) -> Option<Event> {
+        self.pending.take()
+    }
+}
+
+fn watch_raw(
+    on_event: Channel<WatchEvent>,
+    rx: Receiver<notify::Result<Event>>,
+    filter: EventFilter,
+) {
     spawn(move || {
-        while let Ok(event) = rx.recv() {
-            if let Ok(event) = event {
-                // TODO: Should errors be emitted too?
-                let _ = on_event.send(event);
+        let mut pairer = RenamePairer::default();
+        let send = |event: Event| {
+            if filter.accepts(&event) {
+                let _ = on_event.send(WatchEvent::Event(event));
+            }
+        };
+        loop {
+            let event = if pairer.pending.is_some() {
+                match rx.recv_timeout(RENAME_TIMEOUT) {
+                    Ok(event) => event,
+                    // the file was moved out of the watched paths
+                    Err(RecvTimeoutError::Timeout) => {
+                        if let Some(event) = pairer.flush() {
+                            send(event);
+                        }
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            } else {
+                match rx.recv() {
+                    Ok(event) => event,
+                    Err(_) => break,
+                }
+            };
+
+            match event {
+                Ok(event) => pairer.push(event).into_iter().for_each(send),
+                Err(error) => {
+                    let _ = on_event.send(error.into());
+                }
             }
         }
     });
 }
 
-fn watch_debounced(on_event: Channel<Event>, rx: Receiver<DebounceEventResult>) {
+fn watch_debounced(
+    on_event: Channel<WatchEvent>,
+    rx: Receiver<DebounceEventResult>,
+    filter: EventFilter,
+) {
+    // the debouncer already pairs renames
     spawn(move || {
-        while let Ok(Ok(events)) = rx.recv() {
-            for event in events {
-                // TODO: Should errors be emitted too?
-                let _ = on_event.send(event.event);
+        while let Ok(result) = rx.recv() {
+            match result {
+                Ok(events) => {
+                    for event in events {
+                        if filter.accepts(&event.event) {
+                            let _ = on_event.send(WatchEvent::Event(event.event));
+                        }
+                    }
+                }
+                Err(errors) => {
+                    for error in errors {
+                        let _ = on_event.send(error.into());
+                    }
+                }
             }
         }
     });
 }
 
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How the file system changes are detected.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchMode {
+    /// Uses the notifications of the operating system.
+    #[default]
+    Native,
+    /// Scans the watched paths periodically, which works on any file system.
+    Poll,
+    /// Polls the paths on network file systems or when the native watcher can't be created.
+    Auto,
+}
+
 #[derive(Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WatchOptions {
     base_dir: Option<BaseDirectory>,
     recursive: bool,
     delay_ms: Option<u64>,
+    /// Globs of the paths, relative to the watched paths, whose events are dropped
+    #[serde(default)]
+    ignore: Vec<String>,
+    #[serde(default)]
+    mode: WatchMode,
+    /// Interval between two scans of the polling watcher
+    poll_interval_ms: Option<u64>,
+    /// Whether the polling watcher compares the file contents instead of their modification time
+    #[serde(default)]
+    compare_contents: bool,
 }
 
 #[tauri::command]
@@ -86,7 +360,7 @@ pub async fn watch<R: Runtime>(
     webview: Webview<R>,
     paths: Vec<SafeFilePath>,
     options: WatchOptions,
-    on_event: Channel<Event>,
+    on_event: Channel<WatchEvent>,
     global_scope: GlobalScope<Entry>,
     command_scope: CommandScope<Entry>,
 ) -> CommandResult<ResourceId> {
@@ -106,26 +380,49 @@ pub async fn watch<R: Runtime>(
     } else {
         RecursiveMode::NonRecursive
     };
+    let filter = EventFilter::new(&resolved_paths, glob_set(&options.ignore)?);
 
-    let kind = if let Some(delay) = options.delay_ms {
-        let (tx, rx) = channel();
-        let mut debouncer = new_debouncer(Duration::from_millis(delay), None, tx)?;
-        for path in &resolved_paths {
-            debouncer.watcher().watch(path.as_ref(), recursive_mode)?;
-            debouncer.cache().add_root(path, recursive_mode);
-        }
-        watch_debounced(on_event, rx);
-        WatcherKind::Debouncer(debouncer)
-    } else {
-        let (tx, rx) = channel();
-        let mut watcher = RecommendedWatcher::new(tx, Config::default())?;
-        for path in &resolved_paths {
-            watcher.watch(path.as_ref(), recursive_mode)?;
-        }
-        watch_raw(on_event, rx);
-        WatcherKind::Watcher(watcher)
+    let delay = options.delay_ms.map(Duration::from_millis);
+    let start_native = || {
+        start_watcher::<RecommendedWatcher>(
+            &resolved_paths,
+            recursive_mode,
+            delay,
+            Config::default(),
+            WatcherKind::Debouncer,
+            WatcherKind::Watcher,
+        )
+    };
+    let start_poll = || {
+        let config = Config::default()
+            .with_poll_interval(
+                options
+                    .poll_interval_ms
+                    .map(Duration::from_millis)
+                    .unwrap_or(DEFAULT_POLL_INTERVAL),
+            )
+            .with_compare_contents(options.compare_contents);
+        start_watcher::<PollWatcher>(
+            &resolved_paths,
+            recursive_mode,
+            delay,
+            config,
+            WatcherKind::PollDebouncer,
+            WatcherKind::Poll,
+        )
     };
 
+    let (kind, receiver) = match options.mode {
+        WatchMode::Native => start_native()?,
+        WatchMode::Poll => start_poll()?,
+        WatchMode::Auto if resolved_paths.iter().any(|path| is_remote_fs(path)) => start_poll()?,
+        WatchMode::Auto => start_native().or_else(|_| start_poll())?,
+    };
+    match receiver {
+        EventReceiver::Debounced(rx) => watch_debounced(on_event, rx, filter),
+        EventReceiver::Raw(rx) => watch_raw(on_event, rx, filter),
+    }
+
     let rid = webview
         .resources_table()
         .add(WatcherResource::new(kind, resolved_paths));
@@ -137,23 +434,116 @@ pub async fn watch<R: Runtime>(
 pub async fn unwatch<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> CommandResult<()> {
     let watcher = webview.resources_table().take::<WatcherResource>(rid)?;
     WatcherResource::with_lock(&watcher, |watcher| {
-        match &mut watcher.kind {
-            WatcherKind::Debouncer(ref mut debouncer) => {
-                for path in &watcher.paths {
-                    debouncer.watcher().unwatch(path.as_ref()).map_err(|e| {
-                        format!("failed to unwatch path: {} with error: {e}", path.display())
-                    })?;
-                }
-            }
-            WatcherKind::Watcher(ref mut w) => {
-                for path in &watcher.paths {
-                    w.unwatch(path.as_ref()).map_err(|e| {
-                        format!("failed to unwatch path: {} with error: {e}", path.display())
-                    })?;
-                }
-            }
+        for path in &watcher.paths {
+            watcher.kind.watcher().unwatch(path.as_ref()).map_err(|e| {
+                format!("failed to unwatch path: {} with error: {e}", path.display())
+            })?;
         }
 
         Ok(())
     })
 }
+
+mod test {
+    #[test]
+    fn rename_events_are_paired() {
+        use super::RenamePairer;
+        use notify::{
+            event::{CreateKind, ModifyKind, RenameMode},
+            Event, EventKind,
+        };
+        use std::path::PathBuf;
+
+        let rename = |mode, path: &str| {
+            Event::new(EventKind::Modify(ModifyKind::Name(mode)))
+                .add_path(PathBuf::from(path))
+                .set_tracker(1)
+        };
+
+        let mut pairer = RenamePairer::default();
+        assert!(pairer.push(rename(RenameMode::From, "/a")).is_empty());
+        let events = pairer.push(rename(RenameMode::To, "/b"));
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].kind,
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both))
+        );
+        assert_eq!(events[0].paths, [PathBuf::from("/a"), PathBuf::from("/b")]);
+
+        // the backend reports the same rename again
+        let both = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(PathBuf::from("/a"))
+            .add_path(PathBuf::from("/b"));
+        assert!(pairer.push(both).is_empty());
+
+        // a source without destination is sent before the next event
+        assert!(pairer.push(rename(RenameMode::From, "/c")).is_empty());
+        let create = Event::new(EventKind::Create(CreateKind::File)).add_path(PathBuf::from("/d"));
+        let events = pairer.push(create);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].paths, [PathBuf::from("/c")]);
+        assert!(pairer.flush().is_none());
+    }
+
+    #[test]
+    fn ignored_events_are_dropped() {
+        use super::EventFilter;
+        use notify::{event::CreateKind, Event, EventKind};
+        use std::path::PathBuf;
+
+        let ignore = || crate::walk::glob_set(&["**/node_modules/**".into()]).unwrap();
+        let filter = EventFilter::new(&[PathBuf::from("/project")], ignore());
+        let event = |path: &str| {
+            Event::new(EventKind::Create(CreateKind::File)).add_path(PathBuf::from(path))
+        };
+
+        assert!(filter.accepts(&event("/project/src/index.ts")));
+        assert!(!filter.accepts(&event("/project/node_modules/a/index.js")));
+        assert!(!filter.accepts(&event("/project/packages/b/node_modules/c.js")));
+
+        // the events can be reported with the canonical path of the root
+        #[cfg(unix)]
+        {
+            let dir = tempfile::tempdir().unwrap();
+            let dir = dir.path();
+            std::fs::create_dir_all(dir.join("project")).unwrap();
+            std::os::unix::fs::symlink(dir.join("project"), dir.join("link")).unwrap();
+
+            let filter = EventFilter::new(&[dir.join("link")], ignore());
+            let canonical = dunce::canonicalize(dir.join("project")).unwrap();
+            let path = canonical.join("node_modules/a/index.js");
+            assert!(!filter.accepts(&event(path.to_str().unwrap())));
+        }
+    }
+
+    #[test]
+    fn poll_watcher_reports_changes() {
+        use super::{start_watcher, EventReceiver, WatcherKind};
+        use notify::{Config, PollWatcher, RecursiveMode};
+        use std::time::Duration;
+
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let config = Config::default()
+            .with_poll_interval(Duration::from_millis(20))
+            .with_compare_contents(true);
+        let (_watcher, receiver) = start_watcher::<PollWatcher>(
+            &[dir.to_path_buf()],
+            RecursiveMode::Recursive,
+            None,
+            config,
+            WatcherKind::PollDebouncer,
+            WatcherKind::Poll,
+        )
+        .unwrap();
+        let EventReceiver::Raw(rx) = receiver else {
+            panic!("expected a raw watcher");
+        };
+
+        let file = dir.join("data.txt");
+        std::fs::write(&file, "data").unwrap();
+        let event = rx.recv_timeout(Duration::from_secs(5)).unwrap().unwrap();
+        assert!(event.paths.contains(&file));
+    }
+}
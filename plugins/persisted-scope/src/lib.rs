@@ -310,7 +310,9 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
                 if let Some(fs_scope) = fs_scope {
                     let app_ = app.clone();
                     fs_scope.listen(move |event| {
-                        if let tauri_plugin_fs::ScopeEvent::PathAllowed(_) = event {
+                        if let tauri_plugin_fs::ScopeEvent::PathAllowed(_)
+                        | tauri_plugin_fs::ScopeEvent::PathRevoked(_) = event
+                        {
                             save_scopes(app_.fs_scope(), &app_dir, &fs_scope_state_path);
                         }
                     });
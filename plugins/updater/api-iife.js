@@ -1 +1,207 @@
-if("__TAURI__"in window){var __TAURI_PLUGIN_UPDATER__=function(e){"use strict";function t(e,t,s,n){if("a"===s&&!n)throw new TypeError("Private accessor was defined without a getter");if("function"==typeof t?e!==t||!n:!t.has(e))throw new TypeError("Cannot read private member from an object whose class did not declare it");return"m"===s?n:"a"===s?n.call(e):n?n.value:t.get(e)}function s(e,t,s,n,i){if("function"==typeof t?e!==t||!i:!t.has(e))throw new TypeError("Cannot write private member to an object whose class did not declare it");return t.set(e,s),s}var n,i,r,a;"function"==typeof SuppressedError&&SuppressedError;class o{constructor(){this.__TAURI_CHANNEL_MARKER__=!0,n.set(this,(()=>{})),i.set(this,0),r.set(this,{}),this.id=function(e,t=!1){return window.__TAURI_INTERNALS__.transformCallback(e,t)}((({message:e,id:a})=>{if(a===t(this,i,"f")){s(this,i,a+1),t(this,n,"f").call(this,e);const o=Object.keys(t(this,r,"f"));if(o.length>0){let e=a+1;for(const s of o.sort()){if(parseInt(s)!==e)break;{const i=t(this,r,"f")[s];delete t(this,r,"f")[s],t(this,n,"f").call(this,i),e+=1}}s(this,i,e)}}else t(this,r,"f")[a.toString()]=e}))}set onmessage(e){s(this,n,e)}get onmessage(){return t(this,n,"f")}toJSON(){return`__CHANNEL__:${this.id}`}}async function d(e,t={},s){return window.__TAURI_INTERNALS__.invoke(e,t,s)}n=new WeakMap,i=new WeakMap,r=new WeakMap;class l{get rid(){return t(this,a,"f")}constructor(e){a.set(this,void 0),s(this,a,e)}async close(){return d("plugin:resources|close",{rid:this.rid})}}a=new WeakMap;class c extends l{constructor(e){super(e.rid),this.available=e.available,this.currentVersion=e.currentVersion,this.version=e.version,this.date=e.date,this.body=e.body}async download(e,t){const s=new o;e&&(s.onmessage=e);const n=await d("plugin:updater|download",{onEvent:s,rid:this.rid,...t});this.downloadedBytes=new l(n)}async install(){if(!this.downloadedBytes)throw new Error("Update.install called before Update.download");await d("plugin:updater|install",{updateRid:this.rid,bytesRid:this.downloadedBytes.rid}),this.downloadedBytes=void 0}async downloadAndInstall(e,t){const s=new o;e&&(s.onmessage=e),await d("plugin:updater|download_and_install",{onEvent:s,rid:this.rid,...t})}async close(){await(this.downloadedBytes?.close()),await super.close()}}return e.Update=c,e.check=async function(e){return e?.headers&&(e.headers=Array.from(new Headers(e.headers).entries())),await d("plugin:updater|check",{...e}).then((e=>e.available?new c(e):null))},e}({});Object.defineProperty(window.__TAURI__,"updater",{value:__TAURI_PLUGIN_UPDATER__})}
+if ('__TAURI__' in window) {
+var __TAURI_PLUGIN_UPDATER__ = (function (exports) {
+'use strict';
+function transformCallback(callback, once = false) {
+return window.__TAURI_INTERNALS__.transformCallback(callback, once)
+}
+class Channel {
+#onmessage = () => {}
+#nextMessageId = 0
+#pendingMessages = {}
+constructor() {
+this.__TAURI_CHANNEL_MARKER__ = true
+this.id = transformCallback(({ message, id }) => {
+if (id === this.#nextMessageId) {
+this.#nextMessageId = id + 1
+this.#onmessage(message)
+const pendingMessageIds = Object.keys(this.#pendingMessages)
+if (pendingMessageIds.length > 0) {
+let nextId = id + 1
+for (const pendingId of pendingMessageIds.sort()) {
+if (parseInt(pendingId) === nextId) {
+const message = this.#pendingMessages[pendingId]
+delete this.#pendingMessages[pendingId]
+this.#onmessage(message)
+nextId += 1
+} else {
+break
+}
+}
+this.#nextMessageId = nextId
+}
+} else {
+this.#pendingMessages[id.toString()] = message
+}
+})
+}
+set onmessage(handler) {
+this.#onmessage = handler
+}
+get onmessage() {
+return this.#onmessage
+}
+toJSON() {
+return `__CHANNEL__:${this.id}`
+}
+}
+async function invoke(cmd, args = {}, options) {
+return window.__TAURI_INTERNALS__.invoke(cmd, args, options)
+}
+class Resource {
+#rid
+get rid() {
+return this.#rid
+}
+constructor(rid) {
+this.#rid = rid
+}
+async close() {
+return invoke('plugin:resources|close', {
+rid: this.rid
+})
+}
+}
+async function _unlisten(event, eventId) {
+await invoke('plugin:event|unlisten', {
+event,
+eventId
+})
+}
+async function listen(event, handler, options) {
+const target =
+typeof options?.target === 'string'
+? { kind: 'AnyLabel', label: options.target }
+: (options?.target ?? { kind: 'Any' })
+return invoke('plugin:event|listen', {
+event,
+target,
+handler: transformCallback(handler)
+}).then((eventId) => {
+return async () => _unlisten(event, eventId)
+})
+}
+class Update extends Resource {
+constructor(metadata) {
+super(metadata.rid)
+this.available = metadata.available
+this.currentVersion = metadata.currentVersion
+this.version = metadata.version
+this.date = metadata.date
+this.body = metadata.body
+this.mandatory = metadata.mandatory
+this.minimumVersion = metadata.minimumVersion
+}
+async download(
+onEvent,
+options
+){
+const channel = new Channel()
+if (onEvent) {
+channel.onmessage = onEvent
+}
+const downloadedBytesRid = await invoke('plugin:updater|download', {
+onEvent: channel,
+rid: this.rid,
+...options
+})
+this.downloadedBytes = new Resource(downloadedBytesRid)
+}
+async install(){
+if (!this.downloadedBytes) {
+throw new Error('Update.install called before Update.download')
+}
+await invoke('plugin:updater|install', {
+updateRid: this.rid,
+bytesRid: this.downloadedBytes.rid
+})
+this.downloadedBytes = undefined
+}
+async downloadAndInstall(
+onEvent,
+options
+){
+const channel = new Channel()
+if (onEvent) {
+channel.onmessage = onEvent
+}
+await invoke('plugin:updater|download_and_install', {
+onEvent: channel,
+rid: this.rid,
+...options
+})
+}
+async skip(){
+await invoke('plugin:updater|skip', { rid: this.rid })
+}
+async postpone(duration){
+await invoke('plugin:updater|postpone', { rid: this.rid, duration })
+}
+async close(){
+await this.downloadedBytes?.close()
+await super.close()
+}
+}
+async function check(options){
+if (options?.headers) {
+options.headers = Array.from(new Headers(options.headers).entries())
+}
+return await invoke('plugin:updater|check', {
+...options
+}).then((meta) => (meta.available ? new Update(meta) : null))
+}
+async function clearSkipped(){
+await invoke('plugin:updater|clear_skipped')
+}
+async function onUpdateAvailable(
+handler
+){
+return await listen(
+'updater://update-available',
+(event) => {
+handler(event.payload)
+}
+)
+}
+async function onDownloadProgress(
+handler
+){
+return await listen('updater://download-progress', (event) => {
+handler(event.payload)
+})
+}
+async function onReadyToInstall(
+handler
+){
+return await listen(
+'updater://ready-to-install',
+(event) => {
+handler(event.payload)
+}
+)
+}
+async function onCheckFailed(
+handler
+){
+return await listen('updater://check-failed', (event) => {
+handler(event.payload)
+})
+}
+async function markLaunchSuccessful(){
+await invoke('plugin:updater|mark_launch_successful')
+}
+async function rollbackReport(){
+return await invoke('plugin:updater|rollback_report')
+}
+exports.check = check;
+exports.clearSkipped = clearSkipped;
+exports.onUpdateAvailable = onUpdateAvailable;
+exports.onDownloadProgress = onDownloadProgress;
+exports.onReadyToInstall = onReadyToInstall;
+exports.onCheckFailed = onCheckFailed;
+exports.markLaunchSuccessful = markLaunchSuccessful;
+exports.rollbackReport = rollbackReport;
+exports.Update = Update;
+return exports;
+})({});
+Object.defineProperty(window.__TAURI__, 'updater', { value: __TAURI_PLUGIN_UPDATER__ })
+}
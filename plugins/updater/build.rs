@@ -2,7 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-const COMMANDS: &[&str] = &["check", "download", "install", "download_and_install"];
+const COMMANDS: &[&str] = &[
+    "check",
+    "download",
+    "install",
+    "download_and_install",
+    "mark_launch_successful",
+    "rollback_report",
+    "skip",
+    "postpone",
+    "clear_skipped",
+];
 
 fn main() {
     tauri_plugin::Builder::new(COMMANDS)
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 import { invoke, Channel, Resource } from '@tauri-apps/api/core'
+import { listen, type UnlistenFn } from '@tauri-apps/api/event'
 
 /** Options used when checking for updates */
 interface CheckOptions {
@@ -22,6 +23,11 @@ interface CheckOptions {
    * Target identifier for the running application. This is sent to the backend.
    */
   target?: string
+  /**
+   * Whether to return the versions skipped or postponed by the user,
+   * e.g. when the user explicitly checks for updates. Defaults to `false`.
+   */
+  includeSkipped?: boolean
 }
 
 /** Options used when downloading an update */
@@ -43,6 +49,8 @@ interface UpdateMetadata {
   version: string
   date?: string
   body?: string
+  mandatory: boolean
+  minimumVersion?: string
 }
 
 /** Updater download event */
@@ -57,6 +65,14 @@ class Update extends Resource {
   version: string
   date?: string
   body?: string
+  /**
+   * Whether the update must be installed before the app can be used,
+   * either because the release is marked as mandatory, the current version
+   * is lower than {@linkcode Update.minimumVersion} or it has been revoked.
+   */
+  mandatory: boolean
+  /** Minimum version of the app that is still supported. */
+  minimumVersion?: string
   private downloadedBytes?: Resource
 
   constructor(metadata: UpdateMetadata) {
@@ -66,6 +82,8 @@ class Update extends Resource {
     this.version = metadata.version
     this.date = metadata.date
     this.body = metadata.body
+    this.mandatory = metadata.mandatory
+    this.minimumVersion = metadata.minimumVersion
   }
 
   /** Download the updater package */
@@ -116,6 +134,22 @@ class Update extends Resource {
     })
   }
 
+  /**
+   * Skips this update, {@linkcode check} ignores it until a newer version is released.
+   * Mandatory updates are never skipped.
+   */
+  async skip(): Promise<void> {
+    await invoke('plugin:updater|skip', { rid: this.rid })
+  }
+
+  /**
+   * Postpones this update, {@linkcode check} ignores it for `duration` milliseconds
+   * unless a newer version is released. Mandatory updates are never postponed.
+   */
+  async postpone(duration: number): Promise<void> {
+    await invoke('plugin:updater|postpone', { rid: this.rid, duration })
+  }
+
   async close(): Promise<void> {
     await this.downloadedBytes?.close()
     await super.close()
@@ -133,5 +167,125 @@ async function check(options?: CheckOptions): Promise<Update | null> {
   }).then((meta) => (meta.available ? new Update(meta) : null))
 }
 
-export type { CheckOptions, DownloadOptions, DownloadEvent }
-export { check, Update }
+/** Forgets the versions skipped or postponed with {@linkcode Update.skip} and {@linkcode Update.postpone} */
+async function clearSkipped(): Promise<void> {
+  await invoke('plugin:updater|clear_skipped')
+}
+
+/** Payload of the events emitted by the background update checker */
+interface UpdateEventPayload {
+  currentVersion: string
+  version: string
+  date?: string
+  body?: string
+  mandatory: boolean
+  minimumVersion?: string
+}
+
+/**
+ * Listen to the `updater://update-available` event emitted when the background update checker,
+ * configured on the plugin builder, finds an update.
+ */
+async function onUpdateAvailable(
+  handler: (update: UpdateEventPayload) => void
+): Promise<UnlistenFn> {
+  return await listen<UpdateEventPayload>(
+    'updater://update-available',
+    (event) => {
+      handler(event.payload)
+    }
+  )
+}
+
+/**
+ * Listen to the `updater://download-progress` event emitted while the background
+ * update checker downloads an update.
+ */
+async function onDownloadProgress(
+  handler: (progress: DownloadEvent) => void
+): Promise<UnlistenFn> {
+  return await listen<DownloadEvent>('updater://download-progress', (event) => {
+    handler(event.payload)
+  })
+}
+
+/**
+ * Listen to the `updater://ready-to-install` event emitted when the background update checker
+ * downloaded an update. Calling {@linkcode Update.downloadAndInstall} on the update returned by
+ * {@linkcode check} installs the already downloaded package.
+ */
+async function onReadyToInstall(
+  handler: (update: UpdateEventPayload) => void
+): Promise<UnlistenFn> {
+  return await listen<UpdateEventPayload>(
+    'updater://ready-to-install',
+    (event) => {
+      handler(event.payload)
+    }
+  )
+}
+
+/** Why an endpoint did not return a valid release JSON */
+type EndpointError = { url: string } & (
+  | { kind: 'status'; status: number }
+  | { kind: 'transport'; message: string }
+  | { kind: 'parse'; message: string }
+)
+
+/**
+ * Listen to the `updater://check-failed` event emitted when none of the endpoints
+ * returned a valid release JSON, with the error of each endpoint.
+ */
+async function onCheckFailed(
+  handler: (errors: EndpointError[]) => void
+): Promise<UnlistenFn> {
+  return await listen<EndpointError[]>('updater://check-failed', (event) => {
+    handler(event.payload)
+  })
+}
+
+/** Report of an update that failed to launch and was rolled back */
+interface RollbackReport {
+  /** Version of the update that failed to launch */
+  failedVersion: string
+  /** Version of the restored installation */
+  restoredVersion: string
+  /** Number of launches of the failed version */
+  launches: number
+}
+
+/**
+ * Confirms that the app launched successfully after an update, discarding the backup
+ * of the previous installation. Only needed when rollbacks are enabled on the plugin builder.
+ */
+async function markLaunchSuccessful(): Promise<void> {
+  await invoke('plugin:updater|mark_launch_successful')
+}
+
+/**
+ * Gets the report of the update that failed to launch and was rolled back when the app started,
+ * resolves to `null` if no update was rolled back.
+ */
+async function rollbackReport(): Promise<RollbackReport | null> {
+  return await invoke<RollbackReport | null>('plugin:updater|rollback_report')
+}
+
+export type {
+  CheckOptions,
+  DownloadOptions,
+  DownloadEvent,
+  UpdateEventPayload,
+  EndpointError,
+  RollbackReport
+}
+export {
+  check,
+  clearSkipped,
+  onUpdateAvailable,
+  onDownloadProgress,
+  onReadyToInstall,
+  onCheckFailed,
+  markLaunchSuccessful,
+  rollbackReport,
+  Update
+}
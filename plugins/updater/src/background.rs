@@ -0,0 +1,187 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::{commands::DownloadEvent, Error, Update, UpdaterExt};
+
+/// Event emitted when the background checker finds an update, with an [`UpdateEventPayload`].
+pub const UPDATE_AVAILABLE_EVENT: &str = "updater://update-available";
+/// Event emitted while the background checker downloads an update, with a download event payload
+/// as sent to the `onEvent` channel of the `download` command.
+pub const DOWNLOAD_PROGRESS_EVENT: &str = "updater://download-progress";
+/// Event emitted when the background checker downloaded an update, with an [`UpdateEventPayload`].
+///
+/// Calling [`Update::download_and_install`] on the update returned by a new check
+/// installs the already downloaded package.
+pub const READY_TO_INSTALL_EVENT: &str = "updater://ready-to-install";
+
+/// Event emitted when none of the endpoints returned a valid release JSON,
+/// with the list of [`crate::EndpointError`].
+pub const CHECK_FAILED_EVENT: &str = "updater://check-failed";
+
+/// How often the idle state is checked when [`BackgroundCheck::only_when_idle`] is set.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Options of the background update checker, see [`crate::Builder::background_check`].
+#[derive(Debug, Clone)]
+pub struct BackgroundCheck {
+    interval: Duration,
+    jitter: Duration,
+    only_when_idle: bool,
+    auto_download: bool,
+}
+
+impl BackgroundCheck {
+    /// Checks for updates every `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            jitter: Duration::ZERO,
+            only_when_idle: false,
+            auto_download: false,
+        }
+    }
+
+    /// Adds a random delay up to `jitter` to each interval,
+    /// to spread the requests of all running apps over time.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Postpones the checks while one of the app windows is focused.
+    pub fn only_when_idle(mut self, only_when_idle: bool) -> Self {
+        self.only_when_idle = only_when_idle;
+        self
+    }
+
+    /// Downloads the update as soon as it is available.
+    pub fn auto_download(mut self, auto_download: bool) -> Self {
+        self.auto_download = auto_download;
+        self
+    }
+
+    fn next_delay(&self) -> Duration {
+        let jitter = self.jitter.as_millis() as u64;
+        if jitter == 0 {
+            return self.interval;
+        }
+        let random = RandomState::new().build_hasher().finish();
+        self.interval + Duration::from_millis(random % (jitter + 1))
+    }
+}
+
+/// Payload of the [`UPDATE_AVAILABLE_EVENT`] and [`READY_TO_INSTALL_EVENT`] events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateEventPayload {
+    pub current_version: String,
+    pub version: String,
+    pub date: Option<String>,
+    pub body: Option<String>,
+    pub mandatory: bool,
+    pub minimum_version: Option<String>,
+}
+
+impl From<&Update> for UpdateEventPayload {
+    fn from(update: &Update) -> Self {
+        Self {
+            current_version: update.current_version.clone(),
+            version: update.version.clone(),
+            date: update.date.map(|d| d.to_string()),
+            body: update.body.clone(),
+            mandatory: update.mandatory,
+            minimum_version: update.minimum_version.clone(),
+        }
+    }
+}
+
+pub(crate) fn spawn<R: Runtime>(app: AppHandle<R>, options: BackgroundCheck) {
+    tauri::async_runtime::spawn(async move {
+        let mut notified_version = None;
+        let mut downloaded_version = None;
+
+        loop {
+            tokio::time::sleep(options.next_delay()).await;
+
+            if options.only_when_idle {
+                while !is_idle(&app) {
+                    tokio::time::sleep(IDLE_POLL_INTERVAL).await;
+                }
+            }
+
+            let update = match app.updater() {
+                Ok(updater) => updater.check().await,
+                Err(e) => Err(e),
+            };
+            let update = match update {
+                Ok(Some(update)) => update,
+                Ok(None) => continue,
+                Err(Error::EndpointsFailed(errors)) => {
+                    log::warn!("background update check failed: {errors:?}");
+                    let _ = app.emit(CHECK_FAILED_EVENT, errors);
+                    continue;
+                }
+                Err(e) => {
+                    log::warn!("background update check failed: {e}");
+                    continue;
+                }
+            };
+
+            let payload = UpdateEventPayload::from(&update);
+
+            if notified_version.as_ref() != Some(&update.version) {
+                let _ = app.emit(UPDATE_AVAILABLE_EVENT, &payload);
+                notified_version = Some(update.version.clone());
+            }
+
+            if options.auto_download && downloaded_version.as_ref() != Some(&update.version) {
+                let mut first_chunk = true;
+                let downloaded = update
+                    .download_to_file(
+                        |chunk_length, content_length| {
+                            if first_chunk {
+                                first_chunk = !first_chunk;
+                                let _ = app.emit(
+                                    DOWNLOAD_PROGRESS_EVENT,
+                                    DownloadEvent::Started { content_length },
+                                );
+                            }
+                            let _ = app.emit(
+                                DOWNLOAD_PROGRESS_EVENT,
+                                DownloadEvent::Progress { chunk_length },
+                            );
+                        },
+                        || {
+                            let _ = app.emit(DOWNLOAD_PROGRESS_EVENT, DownloadEvent::Finished);
+                        },
+                    )
+                    .await;
+
+                match downloaded {
+                    Ok(_) => {
+                        let _ = app.emit(READY_TO_INSTALL_EVENT, &payload);
+                        downloaded_version = Some(update.version.clone());
+                    }
+                    // the download is resumed on the next check
+                    Err(e) => log::warn!("failed to download update {}: {e}", update.version),
+                }
+            }
+        }
+    });
+}
+
+fn is_idle<R: Runtime>(app: &AppHandle<R>) -> bool {
+    !app.webview_windows()
+        .values()
+        .any(|window| window.is_focused().unwrap_or(false))
+}
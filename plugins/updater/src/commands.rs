@@ -2,13 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::{Result, Update, UpdaterExt};
+use crate::{Error, Result, RollbackReport, Update, UpdaterExt, CHECK_FAILED_EVENT};
 
 use http::{HeaderMap, HeaderName, HeaderValue};
 use serde::Serialize;
-use tauri::{ipc::Channel, Manager, Resource, ResourceId, Runtime, Webview};
+use tauri::{ipc::Channel, Emitter, Manager, Resource, ResourceId, Runtime, Webview};
 
-use std::{str::FromStr, time::Duration};
+use std::{path::PathBuf, str::FromStr, sync::Arc, time::Duration};
 use url::Url;
 
 #[derive(Debug, Clone, Serialize)]
@@ -34,10 +34,17 @@ pub(crate) struct Metadata {
     version: String,
     date: Option<String>,
     body: Option<String>,
+    mandatory: bool,
+    minimum_version: Option<String>,
 }
 
-struct DownloadedBytes(pub Vec<u8>);
-impl Resource for DownloadedBytes {}
+/// An updater package downloaded to disk, removed when the resource is closed.
+struct DownloadedFile(pub PathBuf);
+impl Resource for DownloadedFile {
+    fn close(self: Arc<Self>) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
 
 #[tauri::command]
 pub(crate) async fn check<R: Runtime>(
@@ -46,6 +53,7 @@ pub(crate) async fn check<R: Runtime>(
     timeout: Option<u64>,
     proxy: Option<String>,
     target: Option<String>,
+    include_skipped: Option<bool>,
 ) -> Result<Metadata> {
     let mut builder = webview.updater_builder();
     if let Some(headers) = headers {
@@ -63,9 +71,18 @@ pub(crate) async fn check<R: Runtime>(
     if let Some(target) = target {
         builder = builder.target(target);
     }
+    if let Some(include_skipped) = include_skipped {
+        builder = builder.include_skipped(include_skipped);
+    }
 
     let updater = builder.build()?;
-    let update = updater.check().await?;
+    let update = match updater.check().await {
+        Err(Error::EndpointsFailed(errors)) => {
+            let _ = webview.emit(CHECK_FAILED_EVENT, &errors);
+            return Err(Error::EndpointsFailed(errors));
+        }
+        update => update?,
+    };
     let mut metadata = Metadata::default();
     if let Some(update) = update {
         metadata.available = true;
@@ -73,6 +90,8 @@ pub(crate) async fn check<R: Runtime>(
         metadata.version.clone_from(&update.version);
         metadata.date = update.date.map(|d| d.to_string());
         metadata.body.clone_from(&update.body);
+        metadata.mandatory = update.mandatory;
+        metadata.minimum_version.clone_from(&update.minimum_version);
         metadata.rid = Some(webview.resources_table().add(update));
     }
 
@@ -104,8 +123,8 @@ pub(crate) async fn download<R: Runtime>(
     }
 
     let mut first_chunk = true;
-    let bytes = update
-        .download(
+    let path = update
+        .download_to_file(
             |chunk_length, content_length| {
                 if first_chunk {
                     first_chunk = !first_chunk;
@@ -119,7 +138,7 @@ pub(crate) async fn download<R: Runtime>(
         )
         .await?;
 
-    Ok(webview.resources_table().add(DownloadedBytes(bytes)))
+    Ok(webview.resources_table().add(DownloadedFile(path)))
 }
 
 #[tauri::command]
@@ -129,10 +148,8 @@ pub(crate) async fn install<R: Runtime>(
     bytes_rid: ResourceId,
 ) -> Result<()> {
     let update = webview.resources_table().get::<Update>(update_rid)?;
-    let bytes = webview
-        .resources_table()
-        .get::<DownloadedBytes>(bytes_rid)?;
-    update.install(&bytes.0)?;
+    let file = webview.resources_table().get::<DownloadedFile>(bytes_rid)?;
+    update.install_from_file(&file.0)?;
     let _ = webview.resources_table().close(bytes_rid);
     Ok(())
 }
@@ -180,3 +197,35 @@ pub(crate) async fn download_and_install<R: Runtime>(
 
     Ok(())
 }
+
+#[tauri::command]
+pub(crate) fn mark_launch_successful<R: Runtime>(webview: Webview<R>) -> Result<()> {
+    webview.mark_launch_successful()
+}
+
+#[tauri::command]
+pub(crate) fn rollback_report<R: Runtime>(webview: Webview<R>) -> Option<RollbackReport> {
+    webview.rollback_report()
+}
+
+#[tauri::command]
+pub(crate) fn skip<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> Result<()> {
+    webview.resources_table().get::<Update>(rid)?.skip()
+}
+
+#[tauri::command]
+pub(crate) fn postpone<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+    duration: u64,
+) -> Result<()> {
+    webview
+        .resources_table()
+        .get::<Update>(rid)?
+        .postpone(Duration::from_millis(duration))
+}
+
+#[tauri::command]
+pub(crate) fn clear_skipped<R: Runtime>(webview: Webview<R>) -> Result<()> {
+    webview.updater()?.clear_skipped()
+}
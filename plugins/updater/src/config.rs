@@ -94,11 +94,18 @@ where
 #[derive(Debug, Clone, Default)]
 pub struct Config {
     /// Dangerously allow using insecure transport protocols for update endpoints.
+    ///
+    /// `file://` endpoints are always allowed.
     pub dangerous_insecure_transport_protocol: bool,
     /// Updater endpoints.
     pub endpoints: Vec<Url>,
     /// Signature public key.
     pub pubkey: String,
+    /// Additional signature public keys.
+    ///
+    /// An update is trusted if its signature matches [`Self::pubkey`] or any of these keys,
+    /// which allows rotating the key used to sign updates.
+    pub pubkeys: Vec<String>,
     /// The Windows configuration for the updater.
     pub windows: Option<WindowsConfig>,
 }
@@ -115,12 +122,19 @@ impl<'de> Deserialize<'de> for Config {
             pub dangerous_insecure_transport_protocol: bool,
             #[serde(default)]
             pub endpoints: Vec<Url>,
-            pub pubkey: String,
+            pub pubkey: Option<String>,
+            #[serde(default)]
+            pub pubkeys: Vec<String>,
             pub windows: Option<WindowsConfig>,
         }
 
         let config = Config::deserialize(deserializer)?;
 
+        // `pubkey` can only be omitted when the keys are listed in `pubkeys`
+        if config.pubkey.is_none() && config.pubkeys.is_empty() {
+            return Err(serde::de::Error::missing_field("pubkey"));
+        }
+
         validate_endpoints(
             &config.endpoints,
             config.dangerous_insecure_transport_protocol,
@@ -130,19 +144,30 @@ impl<'de> Deserialize<'de> for Config {
         Ok(Self {
             dangerous_insecure_transport_protocol: config.dangerous_insecure_transport_protocol,
             endpoints: config.endpoints,
-            pubkey: config.pubkey,
+            pubkey: config.pubkey.unwrap_or_default(),
+            pubkeys: config.pubkeys,
             windows: config.windows,
         })
     }
 }
 
+impl Config {
+    /// All the public keys trusted to sign updates.
+    pub(crate) fn trusted_pubkeys(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.pubkey.as_str())
+            .chain(self.pubkeys.iter().map(String::as_str))
+            .filter(|pubkey| !pubkey.is_empty())
+    }
+}
+
 pub(crate) fn validate_endpoints(
     endpoints: &[Url],
     dangerous_insecure_transport_protocol: bool,
 ) -> crate::Result<()> {
     if !dangerous_insecure_transport_protocol {
         for url in endpoints {
-            if url.scheme() != "https" {
+            // local files don't go over the network and their signature is still verified
+            if !matches!(url.scheme(), "https" | "file") {
                 #[cfg(debug_assertions)]
                 {
                     eprintln!("[\x1b[33mWARNING\x1b[0m] The updater endpoint \"{url}\" doesn't use `https` protocol. This is allowed in development but will fail in release builds.");
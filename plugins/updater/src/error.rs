@@ -4,6 +4,7 @@
 
 use serde::{Serialize, Serializer};
 use thiserror::Error;
+use url::Url;
 
 /// All errors that can occur while running the updater.
 #[derive(Debug, Error)]
@@ -12,6 +13,9 @@ pub enum Error {
     /// Endpoints are not sent.
     #[error("Updater does not have any endpoints set.")]
     EmptyEndpoints,
+    /// Public keys are not set.
+    #[error("Updater does not have any public keys set.")]
+    EmptyPubkeys,
     /// IO errors.
     #[error(transparent)]
     Io(#[from] std::io::Error),
@@ -22,8 +26,15 @@ pub enum Error {
     #[error(transparent)]
     Serialization(#[from] serde_json::Error),
     /// Could not fetch a valid response from the server.
+    #[deprecated(
+        since = "2.1.0",
+        note = "no longer returned, `Updater::check` returns `Error::EndpointsFailed` instead"
+    )]
     #[error("Could not fetch a valid release JSON from the remote")]
     ReleaseNotFound,
+    /// None of the endpoints returned a valid release JSON.
+    #[error("Could not fetch a valid release JSON from the endpoints: {}", display_endpoint_errors(.0))]
+    EndpointsFailed(Vec<EndpointError>),
     /// Unsupported app architecture.
     #[error("Unsupported application architecture, expected one of `x86`, `x86_64`, `arm` or `aarch64`.")]
     UnsupportedArch,
@@ -51,6 +62,12 @@ pub enum Error {
     /// `base64` errors.
     #[error(transparent)]
     Base64(#[from] base64::DecodeError),
+    /// The checksum of a downloaded or patched file doesn't match the one announced.
+    #[error("checksum mismatch, expected `{expected}` but got `{actual}`")]
+    ChecksumMismatch { expected: String, actual: String },
+    /// The update can't be postponed for that long.
+    #[error("the update can't be postponed for that long")]
+    InvalidPostponeDuration,
     /// UTF8 Errors in signature.
     #[error("The signature {0} could not be decoded, please check if it is a valid base64 string. The signature must be the contents of the `.sig` file generated by the Tauri bundler, as a string.")]
     SignatureUtf8(String),
@@ -65,6 +82,9 @@ pub enum Error {
     BinaryNotFoundInArchive,
     #[error("invalid updater binary format")]
     InvalidUpdaterFormat,
+    /// The package manager failed to install the `.deb` or `.rpm` package.
+    #[error("failed to install the update package: {0}")]
+    PackageInstallFailed(String),
     #[error(transparent)]
     Http(#[from] http::Error),
     #[error(transparent)]
@@ -88,3 +108,61 @@ impl Serialize for Error {
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+/// Why an endpoint did not return a valid release JSON, see [`Error::EndpointsFailed`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointError {
+    /// The endpoint URL, with its variables replaced
+    pub url: Url,
+    #[serde(flatten)]
+    pub kind: EndpointErrorKind,
+}
+
+#[derive(Debug, Clone, Serialize, Error)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum EndpointErrorKind {
+    /// The endpoint responded with a non-success status code.
+    #[error("status code {status}")]
+    Status { status: u16 },
+    /// The request failed, e.g. because of a timeout, a TLS error or a missing file.
+    #[error("{message}")]
+    Transport { message: String },
+    /// The response is not a valid release JSON.
+    #[error("invalid release JSON: {message}")]
+    Parse { message: String },
+}
+
+impl EndpointError {
+    pub(crate) fn new(url: Url, kind: EndpointErrorKind) -> Self {
+        Self { url, kind }
+    }
+}
+
+impl EndpointErrorKind {
+    pub(crate) fn transport(error: impl std::fmt::Display) -> Self {
+        Self::Transport {
+            message: error.to_string(),
+        }
+    }
+
+    pub(crate) fn parse(error: serde_json::Error) -> Self {
+        Self::Parse {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for EndpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}", self.url, self.kind)
+    }
+}
+
+fn display_endpoint_errors(errors: &[EndpointError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -13,20 +13,30 @@
     html_favicon_url = "https://github.com/tauri-apps/tauri/raw/dev/app-icon.png"
 )]
 
-use std::ffi::OsString;
+use std::{ffi::OsString, path::PathBuf};
 
 use tauri::{
     plugin::{Builder as PluginBuilder, TauriPlugin},
-    Manager, Runtime,
+    AppHandle, Manager, Runtime,
 };
 
+mod background;
 mod commands;
 mod config;
 mod error;
+mod rollback;
+mod skip;
+mod source;
 mod updater;
 
+pub use background::{
+    BackgroundCheck, UpdateEventPayload, CHECK_FAILED_EVENT, DOWNLOAD_PROGRESS_EVENT,
+    READY_TO_INSTALL_EVENT, UPDATE_AVAILABLE_EVENT,
+};
 pub use config::Config;
-pub use error::{Error, Result};
+pub use error::{EndpointError, EndpointErrorKind, Error, Result};
+pub use rollback::RollbackReport;
+pub use source::{FileSource, HttpSource, PackageStream, UpdateSource};
 pub use updater::*;
 
 /// Extensions to [`tauri::App`], [`tauri::AppHandle`], [`tauri::WebviewWindow`], [`tauri::Webview`] and [`tauri::Window`] to access the updater APIs.
@@ -65,13 +75,39 @@ pub trait UpdaterExt<R: Runtime> {
     ///   });
     /// ```
     fn updater(&self) -> Result<Updater>;
+
+    /// Confirms that the app launched successfully after an update,
+    /// discarding the backup of the previous installation.
+    ///
+    /// Only needed when [`Builder::rollback_after_failed_launches`] is set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tauri_plugin_updater::UpdaterExt;
+    /// tauri::Builder::default()
+    ///   .setup(|app| {
+    ///     app.handle().mark_launch_successful()?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    fn mark_launch_successful(&self) -> Result<()>;
+
+    /// Gets the report of the update that failed to launch and was rolled back
+    /// when the app started, see [`Builder::rollback_after_failed_launches`].
+    fn rollback_report(&self) -> Option<RollbackReport>;
 }
 
 impl<R: Runtime, T: Manager<R>> UpdaterExt<R> for T {
     fn updater_builder(&self) -> UpdaterBuilder {
         let app = self.app_handle();
         let package_info = app.package_info();
-        let UpdaterState { config, target } = self.state::<UpdaterState>().inner();
+        let UpdaterState {
+            config,
+            target,
+            keep_backup,
+            ..
+        } = self.state::<UpdaterState>().inner();
 
         let mut builder = UpdaterBuilder::new(
             package_info.name.clone(),
@@ -83,6 +119,12 @@ impl<R: Runtime, T: Manager<R>> UpdaterExt<R> for T {
             builder = builder.target(target);
         }
 
+        builder = builder.keep_backup(*keep_backup);
+
+        if let Ok(dir) = state_dir(app) {
+            builder = builder.state_dir(dir);
+        }
+
         let args = self.env().args_os;
         if !args.is_empty() {
             builder = builder.current_exe_args(args);
@@ -113,18 +155,38 @@ impl<R: Runtime, T: Manager<R>> UpdaterExt<R> for T {
     fn updater(&self) -> Result<Updater> {
         self.updater_builder().build()
     }
+
+    fn mark_launch_successful(&self) -> Result<()> {
+        let app = self.app_handle();
+        rollback::confirm_launch(&state_dir(app)?, &app.package_info().version)
+    }
+
+    fn rollback_report(&self) -> Option<RollbackReport> {
+        self.state::<UpdaterState>().rollback_report.clone()
+    }
+}
+
+/// Directory where the updater keeps the state that must survive restarts,
+/// e.g. the skipped updates and the backup of the previous installation.
+fn state_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf> {
+    Ok(app.path().app_local_data_dir()?.join("updater"))
 }
 
 struct UpdaterState {
     target: Option<String>,
     config: Config,
+    keep_backup: bool,
+    rollback_report: Option<RollbackReport>,
 }
 
 #[derive(Default)]
 pub struct Builder {
     target: Option<String>,
     pubkey: Option<String>,
+    pubkeys: Option<Vec<String>>,
     installer_args: Vec<OsString>,
+    background_check: Option<BackgroundCheck>,
+    rollback_after_failed_launches: Option<u32>,
 }
 
 impl Builder {
@@ -142,6 +204,17 @@ impl Builder {
         self
     }
 
+    /// Sets the additional public keys trusted to sign updates, used to rotate the signing key.
+    pub fn pubkeys<I, S>(mut self, pubkeys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.pubkeys
+            .replace(pubkeys.into_iter().map(Into::into).collect());
+        self
+    }
+
     pub fn installer_args<I, S>(mut self, args: I) -> Self
     where
         I: IntoIterator<Item = S>,
@@ -165,20 +238,111 @@ impl Builder {
         self
     }
 
+    /// Periodically checks for updates in the background, emitting the
+    /// [`UPDATE_AVAILABLE_EVENT`], [`DOWNLOAD_PROGRESS_EVENT`] and [`READY_TO_INSTALL_EVENT`]
+    /// events to the Rust listeners and the webviews.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use tauri::Listener;
+    /// use tauri_plugin_updater::{BackgroundCheck, READY_TO_INSTALL_EVENT};
+    /// tauri::Builder::default()
+    ///   .plugin(
+    ///     tauri_plugin_updater::Builder::new()
+    ///       .background_check(
+    ///         BackgroundCheck::new(Duration::from_secs(60 * 60))
+    ///           .jitter(Duration::from_secs(5 * 60))
+    ///           .auto_download(true),
+    ///       )
+    ///       .build(),
+    ///   )
+    ///   .setup(|app| {
+    ///     app.listen(READY_TO_INSTALL_EVENT, |event| {
+    ///       println!("update downloaded: {}", event.payload());
+    ///     });
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn background_check(mut self, options: BackgroundCheck) -> Self {
+        self.background_check.replace(options);
+        self
+    }
+
+    /// Keeps a backup of the current installation when installing an update and restores it
+    /// if the update does not call [`UpdaterExt::mark_launch_successful`] within `launches` launches.
+    ///
+    /// The app is restarted after restoring the backup,
+    /// the failure is then available with [`UpdaterExt::rollback_report`].
+    ///
+    /// Currently only supported for AppImage installations on Linux.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tauri_plugin_updater::UpdaterExt;
+    /// tauri::Builder::default()
+    ///   .plugin(
+    ///     tauri_plugin_updater::Builder::new()
+    ///       .rollback_after_failed_launches(3)
+    ///       .build(),
+    ///   )
+    ///   .setup(|app| {
+    ///     if let Some(report) = app.rollback_report() {
+    ///       println!("version {} failed to launch", report.failed_version);
+    ///     }
+    ///     app.handle().mark_launch_successful()?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn rollback_after_failed_launches(mut self, launches: u32) -> Self {
+        self.rollback_after_failed_launches.replace(launches.max(1));
+        self
+    }
+
     pub fn build<R: Runtime>(self) -> TauriPlugin<R, Config> {
         let pubkey = self.pubkey;
+        let pubkeys = self.pubkeys;
         let target = self.target;
         let installer_args = self.installer_args;
+        let background_check = self.background_check;
+        let rollback_after_failed_launches = self.rollback_after_failed_launches;
         PluginBuilder::<R, Config>::new("updater")
             .setup(move |app, api| {
                 let mut config = api.config().clone();
                 if let Some(pubkey) = pubkey {
                     config.pubkey = pubkey;
                 }
+                if let Some(pubkeys) = pubkeys {
+                    config.pubkeys = pubkeys;
+                }
                 if let Some(windows) = &mut config.windows {
                     windows.installer_args.extend_from_slice(&installer_args);
                 }
-                app.manage(UpdaterState { target, config });
+
+                let mut rollback_report = None;
+                if let Some(launches) = rollback_after_failed_launches {
+                    match rollback::on_launch(
+                        &state_dir(app)?,
+                        &app.package_info().version,
+                        launches,
+                    )? {
+                        rollback::Launch::Continue => {}
+                        rollback::Launch::RolledBack(report) => rollback_report = Some(report),
+                        rollback::Launch::Restart => app.restart(),
+                    }
+                }
+
+                app.manage(UpdaterState {
+                    target,
+                    config,
+                    keep_backup: rollback_after_failed_launches.is_some(),
+                    rollback_report,
+                });
+                if let Some(options) = background_check {
+                    background::spawn(app.clone(), options);
+                }
                 Ok(())
             })
             .invoke_handler(tauri::generate_handler![
@@ -186,6 +350,11 @@ impl Builder {
                 commands::download,
                 commands::install,
                 commands::download_and_install,
+                commands::mark_launch_successful,
+                commands::rollback_report,
+                commands::skip,
+                commands::postpone,
+                commands::clear_skipped,
             ])
             .build()
     }
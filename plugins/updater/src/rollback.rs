@@ -0,0 +1,245 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Restores the previous installation when an update fails to launch,
+//! see [`crate::Builder::rollback_after_failed_launches`].
+
+use std::path::{Path, PathBuf};
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+const STATE_FILE_NAME: &str = "pending-update.json";
+const BACKUP_FILE_NAME: &str = "previous-installation";
+
+/// Report of an update that was rolled back because its launch was never confirmed
+/// with [`crate::UpdaterExt::mark_launch_successful`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollbackReport {
+    /// Version of the update that failed to launch
+    pub failed_version: String,
+    /// Version of the restored installation
+    pub restored_version: String,
+    /// Number of launches of the failed version
+    pub launches: u32,
+}
+
+/// An installed update waiting for its launch to be confirmed.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PendingUpdate {
+    /// Path of the updated installation
+    pub target: PathBuf,
+    /// Backup of the previous installation
+    pub backup: PathBuf,
+    pub previous_version: String,
+    pub version: String,
+    pub launches: u32,
+    #[serde(default)]
+    pub rolled_back: bool,
+}
+
+/// Outcome of [`on_launch`].
+pub(crate) enum Launch {
+    /// Nothing to do
+    Continue,
+    /// The previous installation was restored on a past launch
+    RolledBack(RollbackReport),
+    /// The previous installation was restored, the app must be restarted
+    Restart,
+}
+
+impl PendingUpdate {
+    fn state_path(dir: &Path) -> PathBuf {
+        dir.join(STATE_FILE_NAME)
+    }
+
+    #[allow(unused)]
+    pub(crate) fn backup_path(dir: &Path) -> PathBuf {
+        dir.join(BACKUP_FILE_NAME)
+    }
+
+    pub(crate) fn read(dir: &Path) -> Result<Option<Self>> {
+        let path = Self::state_path(dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read(&path)?;
+        match serde_json::from_slice(&contents) {
+            Ok(state) => Ok(Some(state)),
+            // a corrupted state can't be acted upon
+            Err(_) => {
+                let _ = std::fs::remove_file(path);
+                Ok(None)
+            }
+        }
+    }
+
+    pub(crate) fn write(&self, dir: &Path) -> Result<()> {
+        let path = Self::state_path(dir);
+        std::fs::write(path, serde_json::to_vec(self)?)?;
+        Ok(())
+    }
+
+    /// Removes the state and the backup of the previous installation.
+    pub(crate) fn discard(self, dir: &Path) -> Result<()> {
+        let _ = std::fs::remove_file(&self.backup);
+        std::fs::remove_file(Self::state_path(dir))?;
+        Ok(())
+    }
+
+    fn restore(&self) -> Result<()> {
+        move_file(&self.backup, &self.target)
+    }
+
+    fn report(&self) -> RollbackReport {
+        RollbackReport {
+            failed_version: self.version.clone(),
+            restored_version: self.previous_version.clone(),
+            launches: self.launches,
+        }
+    }
+}
+
+/// Counts the launches of an installed update and restores the previous
+/// installation once `max_launches` launches were not confirmed.
+///
+/// `dir` is the directory where the state of the pending update is kept.
+pub(crate) fn on_launch(
+    dir: &Path,
+    current_version: &Version,
+    max_launches: u32,
+) -> Result<Launch> {
+    let Some(mut pending) = PendingUpdate::read(dir)? else {
+        return Ok(Launch::Continue);
+    };
+
+    if pending.rolled_back {
+        let report = pending.report();
+        pending.discard(dir)?;
+        return Ok(Launch::RolledBack(report));
+    }
+
+    // the update was not installed or the app was replaced by another version
+    if pending.version != current_version.to_string() {
+        pending.discard(dir)?;
+        return Ok(Launch::Continue);
+    }
+
+    if pending.launches >= max_launches {
+        pending.restore()?;
+        pending.rolled_back = true;
+        pending.write(dir)?;
+        return Ok(Launch::Restart);
+    }
+
+    pending.launches += 1;
+    pending.write(dir)?;
+    Ok(Launch::Continue)
+}
+
+/// Confirms the launch of an installed update, discarding the previous installation.
+pub(crate) fn confirm_launch(dir: &Path, current_version: &Version) -> Result<()> {
+    match PendingUpdate::read(dir)? {
+        Some(pending) if !pending.rolled_back && pending.version == current_version.to_string() => {
+            pending.discard(dir)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Renames `from` to `to`, copying it next to `to` first when they are not on the same mount point
+/// so `to` is never partially written.
+pub(crate) fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if std::fs::rename(from, to).is_err() {
+        let mut copy = to.as_os_str().to_owned();
+        copy.push(".restore");
+        let copy = PathBuf::from(copy);
+        std::fs::copy(from, &copy)?;
+        std::fs::rename(&copy, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_restores_the_backup_after_unconfirmed_launches() {
+        let state_dir = tempfile::tempdir().unwrap();
+        let state_dir = state_dir.path();
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("app.AppImage");
+        let backup = dir.path().join("backup.AppImage");
+        std::fs::write(&target, "new").unwrap();
+        std::fs::write(&backup, "previous").unwrap();
+
+        PendingUpdate {
+            target: target.clone(),
+            backup: backup.clone(),
+            previous_version: "1.0.0".into(),
+            version: "1.1.0".into(),
+            launches: 0,
+            rolled_back: false,
+        }
+        .write(state_dir)
+        .unwrap();
+
+        let version = Version::new(1, 1, 0);
+        for _ in 0..2 {
+            assert!(matches!(
+                on_launch(state_dir, &version, 2).unwrap(),
+                Launch::Continue
+            ));
+        }
+        assert!(matches!(
+            on_launch(state_dir, &version, 2).unwrap(),
+            Launch::Restart
+        ));
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "previous");
+        assert!(!backup.exists());
+
+        let Launch::RolledBack(report) = on_launch(state_dir, &Version::new(1, 0, 0), 2).unwrap()
+        else {
+            panic!("expected a rollback report");
+        };
+        assert_eq!(report.failed_version, "1.1.0");
+        assert_eq!(report.restored_version, "1.0.0");
+        assert_eq!(report.launches, 2);
+        assert!(PendingUpdate::read(state_dir).unwrap().is_none());
+    }
+
+    #[test]
+    fn it_discards_the_backup_once_the_launch_is_confirmed() {
+        let state_dir = tempfile::tempdir().unwrap();
+        let state_dir = state_dir.path();
+        let dir = tempfile::tempdir().unwrap();
+        let backup = dir.path().join("backup.AppImage");
+        std::fs::write(&backup, "previous").unwrap();
+
+        PendingUpdate {
+            target: dir.path().join("app.AppImage"),
+            backup: backup.clone(),
+            previous_version: "1.0.0".into(),
+            version: "1.1.0".into(),
+            launches: 1,
+            rolled_back: false,
+        }
+        .write(state_dir)
+        .unwrap();
+
+        // the previous version can't confirm the launch of the update
+        confirm_launch(state_dir, &Version::new(1, 0, 0)).unwrap();
+        assert!(backup.exists());
+
+        confirm_launch(state_dir, &Version::new(1, 1, 0)).unwrap();
+        assert!(!backup.exists());
+        assert!(PendingUpdate::read(state_dir).unwrap().is_none());
+    }
+}
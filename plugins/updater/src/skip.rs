@@ -0,0 +1,142 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Versions skipped or postponed by the user, see [`crate::Update::skip`]
+//! and [`crate::Update::postpone`].
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{Error, Result};
+
+const STATE_FILE_NAME: &str = "skipped.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SkipState {
+    /// Releases up to this version are ignored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    skipped_version: Option<Version>,
+    /// Releases up to this version are ignored until `postponed_until`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    postponed_version: Option<Version>,
+    /// Unix timestamp in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    postponed_until: Option<i64>,
+}
+
+impl SkipState {
+    fn path(dir: &Path) -> PathBuf {
+        dir.join(STATE_FILE_NAME)
+    }
+
+    /// Reads the state kept in `dir`.
+    pub(crate) fn read(dir: &Path) -> Result<Self> {
+        let path = Self::path(dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        // a corrupted state is ignored, the user will be prompted again
+        Ok(serde_json::from_slice(&std::fs::read(path)?).unwrap_or_default())
+    }
+
+    fn write(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(Self::path(dir), serde_json::to_vec(self)?)?;
+        Ok(())
+    }
+
+    /// Ignores `version` and older releases until a newer one is available.
+    pub(crate) fn skip(dir: &Path, version: Version) -> Result<()> {
+        let mut state = Self::read(dir)?;
+        state.skipped_version.replace(version);
+        state.write(dir)
+    }
+
+    /// Ignores `version` and older releases for `duration`.
+    pub(crate) fn postpone(dir: &Path, version: Version, duration: Duration) -> Result<()> {
+        let until = time::Duration::try_from(duration)
+            .ok()
+            .and_then(|duration| OffsetDateTime::now_utc().checked_add(duration))
+            .ok_or(Error::InvalidPostponeDuration)?;
+
+        let mut state = Self::read(dir)?;
+        state.postponed_version.replace(version);
+        state.postponed_until.replace(until.unix_timestamp());
+        state.write(dir)
+    }
+
+    pub(crate) fn clear(dir: &Path) -> Result<()> {
+        let path = Self::path(dir);
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Whether a release of `version` was skipped or postponed at `now`.
+    pub(crate) fn ignores(&self, version: &Version, now: OffsetDateTime) -> bool {
+        let skipped = self
+            .skipped_version
+            .as_ref()
+            .is_some_and(|skipped| version <= skipped);
+        let postponed = match (&self.postponed_version, self.postponed_until) {
+            (Some(postponed), Some(until)) => version <= postponed && now.unix_timestamp() < until,
+            _ => false,
+        };
+        skipped || postponed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_ignores_skipped_and_postponed_versions() {
+        let now = OffsetDateTime::now_utc();
+        let state = SkipState {
+            skipped_version: Some(Version::new(1, 1, 0)),
+            postponed_version: Some(Version::new(1, 2, 0)),
+            postponed_until: Some((now + Duration::from_secs(60)).unix_timestamp()),
+        };
+
+        assert!(state.ignores(&Version::new(1, 1, 0), now));
+        assert!(state.ignores(&Version::new(1, 2, 0), now));
+        assert!(!state.ignores(&Version::new(1, 3, 0), now));
+
+        // the postponement expired
+        let later = now + Duration::from_secs(120);
+        assert!(state.ignores(&Version::new(1, 1, 0), later));
+        assert!(!state.ignores(&Version::new(1, 2, 0), later));
+    }
+
+    #[test]
+    fn it_persists_skipped_versions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("updater");
+
+        SkipState::skip(&dir, Version::new(1, 1, 0)).unwrap();
+        SkipState::postpone(&dir, Version::new(1, 2, 0), Duration::from_secs(60)).unwrap();
+        assert!(matches!(
+            SkipState::postpone(&dir, Version::new(1, 3, 0), Duration::MAX),
+            Err(Error::InvalidPostponeDuration)
+        ));
+
+        let state = SkipState::read(&dir).unwrap();
+        let now = OffsetDateTime::now_utc();
+        assert!(state.ignores(&Version::new(1, 1, 0), now));
+        assert!(state.ignores(&Version::new(1, 2, 0), now));
+
+        SkipState::clear(&dir).unwrap();
+        let state = SkipState::read(&dir).unwrap();
+        assert!(!state.ignores(&Version::new(1, 1, 0), now));
+    }
+}
@@ -0,0 +1,483 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use futures_util::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt};
+use http::{
+    header::{CONTENT_RANGE, RANGE},
+    HeaderMap, HeaderValue, StatusCode,
+};
+use reqwest::ClientBuilder;
+use url::Url;
+
+use crate::{error::EndpointErrorKind, Error, Result};
+
+const UPDATER_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Size of the chunks read by [`FileSource`].
+const FILE_CHUNK_SIZE: usize = 64 * 1024;
+/// File name of the release manifest in the directories read by [`FileSource`].
+const MANIFEST_FILE_NAME: &str = "latest.json";
+
+/// Where the updater fetches the release manifests and the update packages from,
+/// see [`crate::UpdaterBuilder::source`].
+///
+/// The URLs are the updater endpoints for [`UpdateSource::fetch_manifest`]
+/// and the ones announced by the release manifest for [`UpdateSource::download`].
+pub trait UpdateSource: Send + Sync {
+    /// Fetches the release manifest at `url`, resolves to `None` if no update is available.
+    fn fetch_manifest<'a>(
+        &'a self,
+        url: &'a Url,
+        headers: &'a HeaderMap,
+    ) -> BoxFuture<'a, std::result::Result<Option<Vec<u8>>, EndpointErrorKind>>;
+
+    /// Downloads the update package at `url`, starting at `offset` bytes if possible.
+    fn download<'a>(
+        &'a self,
+        url: &'a Url,
+        headers: &'a HeaderMap,
+        offset: u64,
+    ) -> BoxFuture<'a, Result<PackageStream>>;
+}
+
+/// An update package being downloaded by an [`UpdateSource`].
+pub struct PackageStream {
+    /// Whether the stream starts at the requested offset, otherwise it starts at the first byte.
+    pub resumed: bool,
+    /// Length of the stream.
+    pub content_length: Option<u64>,
+    /// The package bytes.
+    pub chunks: BoxStream<'static, Result<Vec<u8>>>,
+}
+
+/// Fetches `http://` and `https://` URLs with [`reqwest`].
+pub struct HttpSource {
+    client: reqwest::Client,
+}
+
+impl HttpSource {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+
+    pub(crate) fn with_options(timeout: Option<Duration>, proxy: Option<&Url>) -> Result<Self> {
+        let mut request = ClientBuilder::new().user_agent(UPDATER_USER_AGENT);
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        if let Some(proxy) = proxy {
+            let proxy = reqwest::Proxy::all(proxy.as_str())?;
+            request = request.proxy(proxy);
+        }
+        Ok(Self::new(request.build()?))
+    }
+}
+
+impl UpdateSource for HttpSource {
+    fn fetch_manifest<'a>(
+        &'a self,
+        url: &'a Url,
+        headers: &'a HeaderMap,
+    ) -> BoxFuture<'a, std::result::Result<Option<Vec<u8>>, EndpointErrorKind>> {
+        async move {
+            let res = self
+                .client
+                .get(url.clone())
+                .headers(headers.clone())
+                .send()
+                .await
+                .map_err(EndpointErrorKind::transport)?;
+
+            if !res.status().is_success() {
+                return Err(EndpointErrorKind::Status {
+                    status: res.status().as_u16(),
+                });
+            }
+
+            // no updates found!
+            if StatusCode::NO_CONTENT == res.status() {
+                return Ok(None);
+            };
+
+            let body = res.bytes().await.map_err(EndpointErrorKind::transport)?;
+            Ok(Some(body.to_vec()))
+        }
+        .boxed()
+    }
+
+    fn download<'a>(
+        &'a self,
+        url: &'a Url,
+        headers: &'a HeaderMap,
+        offset: u64,
+    ) -> BoxFuture<'a, Result<PackageStream>> {
+        async move {
+            let mut headers = headers.clone();
+            if offset > 0 {
+                headers.insert(RANGE, HeaderValue::from_str(&format!("bytes={offset}-"))?);
+            }
+
+            let mut response = self
+                .client
+                .get(url.clone())
+                .headers(headers.clone())
+                .send()
+                .await?;
+
+            // the partial file is larger than the remote file, it is probably outdated
+            if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+                headers.remove(RANGE);
+                response = self.client.get(url.clone()).headers(headers).send().await?;
+            }
+
+            if !response.status().is_success() {
+                return Err(Error::Network(format!(
+                    "Download request failed with status: {}",
+                    response.status()
+                )));
+            }
+
+            let content_length: Option<u64> = response
+                .headers()
+                .get("Content-Length")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok());
+
+            let resumed = response.status() == StatusCode::PARTIAL_CONTENT
+                && content_range_start(response.headers()) == Some(offset);
+
+            Ok(PackageStream {
+                resumed,
+                content_length,
+                chunks: response
+                    .bytes_stream()
+                    .map(|chunk| chunk.map(|c| c.to_vec()).map_err(Into::into))
+                    .boxed(),
+            })
+        }
+        .boxed()
+    }
+}
+
+/// Reads `file://` URLs from the local file system,
+/// e.g. to distribute updates on removable drives.
+///
+/// A `file://` endpoint can point to a directory, the release manifest is then read from
+/// its `latest.json` file.
+#[derive(Debug, Default, Clone)]
+pub struct FileSource {
+    root: Option<PathBuf>,
+}
+
+impl FileSource {
+    /// Reads `file://` URLs only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the release manifest and the update packages from the `root` directory.
+    ///
+    /// URLs that are not `file://` URLs are resolved to the file of `root` with the same name,
+    /// e.g. a manifest announcing `https://example.com/app_1.1.0_x64-setup.exe` is downloaded
+    /// from `root/app_1.1.0_x64-setup.exe`, so the files of a release can be copied as is.
+    pub fn directory(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: Some(root.into()),
+        }
+    }
+
+    /// The directory the packages of a release manifest read from the `file://` endpoint `url` are in.
+    pub(crate) fn release_root(url: &Url) -> Option<PathBuf> {
+        let path = url.to_file_path().ok()?;
+        if path.is_dir() {
+            Some(path)
+        } else {
+            path.parent().map(Path::to_path_buf)
+        }
+    }
+
+    fn path(&self, url: &Url) -> Result<PathBuf> {
+        if url.scheme() == "file" {
+            return url
+                .to_file_path()
+                .map_err(|_| Error::Network(format!("`{url}` is not a valid file URL")));
+        }
+
+        let Some(root) = &self.root else {
+            return Err(Error::Network(format!("`{url}` is not a file URL")));
+        };
+        url.path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .and_then(|name| {
+                percent_encoding::percent_decode_str(name)
+                    .decode_utf8()
+                    .ok()
+            })
+            // the name must not escape the root directory
+            .filter(|name| {
+                !name.is_empty() && *name != "." && *name != ".." && !name.contains(['/', '\\'])
+            })
+            .map(|name| root.join(&*name))
+            .ok_or_else(|| Error::Network(format!("`{url}` does not name a file")))
+    }
+
+    fn manifest_path(&self, url: &Url) -> Result<PathBuf> {
+        let path = self.path(url)?;
+        if path.is_dir() {
+            Ok(path.join(MANIFEST_FILE_NAME))
+        } else if url.scheme() != "file" && !path.is_file() {
+            // the endpoint of an online server, e.g. `https://example.com/{{target}}/{{current_version}}`
+            Ok(self
+                .root
+                .as_deref()
+                .unwrap_or(&path)
+                .join(MANIFEST_FILE_NAME))
+        } else {
+            Ok(path)
+        }
+    }
+}
+
+impl UpdateSource for FileSource {
+    fn fetch_manifest<'a>(
+        &'a self,
+        url: &'a Url,
+        _headers: &'a HeaderMap,
+    ) -> BoxFuture<'a, std::result::Result<Option<Vec<u8>>, EndpointErrorKind>> {
+        async move {
+            let path = self
+                .manifest_path(url)
+                .map_err(EndpointErrorKind::transport)?;
+            tauri::async_runtime::spawn_blocking(move || std::fs::read(path))
+                .await
+                .map_err(EndpointErrorKind::transport)?
+                .map(Some)
+                .map_err(EndpointErrorKind::transport)
+        }
+        .boxed()
+    }
+
+    fn download<'a>(
+        &'a self,
+        url: &'a Url,
+        _headers: &'a HeaderMap,
+        offset: u64,
+    ) -> BoxFuture<'a, Result<PackageStream>> {
+        async move {
+            let path = self.path(url)?;
+            let (file, len, resumed) = tauri::async_runtime::spawn_blocking(move || {
+                let mut file = std::fs::File::open(&path)?;
+                let len = file.metadata()?.len();
+                let resumed = offset > 0 && offset <= len;
+                if resumed {
+                    file.seek(SeekFrom::Start(offset))?;
+                }
+                Ok::<_, std::io::Error>((file, len, resumed))
+            })
+            .await?
+            // like an unreachable server, so the mirrors are tried
+            .map_err(|e| Error::Network(format!("failed to read `{url}`: {e}")))?;
+
+            let chunks = futures_util::stream::unfold(Some(file), |file| async move {
+                let mut file = file?;
+                let read = tauri::async_runtime::spawn_blocking(move || {
+                    let mut chunk = vec![0; FILE_CHUNK_SIZE];
+                    file.read(&mut chunk).map(|n| {
+                        chunk.truncate(n);
+                        (chunk, file)
+                    })
+                })
+                .await;
+                match read {
+                    Ok(Ok((chunk, _))) if chunk.is_empty() => None,
+                    Ok(Ok((chunk, file))) => Some((Ok(chunk), Some(file))),
+                    Ok(Err(e)) => Some((Err(e.into()), None)),
+                    Err(e) => Some((Err(e.into()), None)),
+                }
+            });
+
+            Ok(PackageStream {
+                resumed,
+                content_length: Some(if resumed { len - offset } else { len }),
+                chunks: chunks.boxed(),
+            })
+        }
+        .boxed()
+    }
+}
+
+/// The source used when none is set on the [`crate::UpdaterBuilder`],
+/// reading `file://` URLs with [`FileSource`] and everything else with [`HttpSource`].
+pub(crate) struct DefaultSource {
+    http: HttpSource,
+    file: FileSource,
+}
+
+impl DefaultSource {
+    pub(crate) fn new(timeout: Option<Duration>, proxy: Option<&Url>) -> Result<Self> {
+        Ok(Self {
+            http: HttpSource::with_options(timeout, proxy)?,
+            file: FileSource::new(),
+        })
+    }
+
+    fn source(&self, url: &Url) -> &dyn UpdateSource {
+        if url.scheme() == "file" {
+            &self.file
+        } else {
+            &self.http
+        }
+    }
+}
+
+impl UpdateSource for DefaultSource {
+    fn fetch_manifest<'a>(
+        &'a self,
+        url: &'a Url,
+        headers: &'a HeaderMap,
+    ) -> BoxFuture<'a, std::result::Result<Option<Vec<u8>>, EndpointErrorKind>> {
+        self.source(url).fetch_manifest(url, headers)
+    }
+
+    fn download<'a>(
+        &'a self,
+        url: &'a Url,
+        headers: &'a HeaderMap,
+        offset: u64,
+    ) -> BoxFuture<'a, Result<PackageStream>> {
+        self.source(url).download(url, headers, offset)
+    }
+}
+
+/// Parses the first byte position of a `Content-Range: bytes <start>-<end>/<size>` header.
+fn content_range_start(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .strip_prefix("bytes ")?
+        .split_once('-')?
+        .0
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_parses_content_range_start() {
+        use super::content_range_start;
+        use reqwest::header::{HeaderMap, HeaderValue, CONTENT_RANGE};
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(content_range_start(&headers), None);
+
+        headers.insert(
+            CONTENT_RANGE,
+            HeaderValue::from_static("bytes 1024-2047/2048"),
+        );
+        assert_eq!(content_range_start(&headers), Some(1024));
+
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes */2048"));
+        assert_eq!(content_range_start(&headers), None);
+    }
+
+    #[test]
+    fn it_reads_file_urls_from_an_offset() {
+        use super::{FileSource, UpdateSource};
+        use futures_util::StreamExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.tar.gz");
+        std::fs::write(&path, b"0123456789").unwrap();
+        let url = url::Url::from_file_path(&path).unwrap();
+        let headers = Default::default();
+
+        tauri::async_runtime::block_on(async {
+            let manifest = FileSource::new()
+                .fetch_manifest(&url, &headers)
+                .await
+                .unwrap();
+            assert_eq!(manifest.as_deref(), Some(&b"0123456789"[..]));
+
+            let mut package = FileSource::new().download(&url, &headers, 4).await.unwrap();
+            assert!(package.resumed);
+            assert_eq!(package.content_length, Some(6));
+            let mut bytes = Vec::new();
+            while let Some(chunk) = package.chunks.next().await {
+                bytes.extend(chunk.unwrap());
+            }
+            assert_eq!(bytes, b"456789");
+
+            // the local file is smaller than the partial download, start over
+            let package = FileSource::new()
+                .download(&url, &headers, 20)
+                .await
+                .unwrap();
+            assert!(!package.resumed);
+            assert_eq!(package.content_length, Some(10));
+
+            let missing = url.join("missing.json").unwrap();
+            assert!(FileSource::new()
+                .fetch_manifest(&missing, &headers)
+                .await
+                .is_err());
+            // missing packages are reported like unreachable servers so the mirrors are tried
+            assert!(matches!(
+                FileSource::new().download(&missing, &headers, 0).await,
+                Err(crate::Error::Network(_))
+            ));
+        });
+    }
+
+    #[test]
+    fn it_reads_releases_from_a_directory() {
+        use super::{FileSource, UpdateSource};
+        use futures_util::StreamExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("latest.json"), b"{}").unwrap();
+        std::fs::write(dir.path().join("app 1.1.0.AppImage"), b"package").unwrap();
+        let dir_url = url::Url::from_directory_path(dir.path()).unwrap();
+        let headers = Default::default();
+
+        assert_eq!(FileSource::release_root(&dir_url).unwrap(), dir.path());
+        assert_eq!(
+            FileSource::release_root(&dir_url.join("latest.json").unwrap()).unwrap(),
+            dir.path()
+        );
+
+        tauri::async_runtime::block_on(async {
+            let manifest = FileSource::new()
+                .fetch_manifest(&dir_url, &headers)
+                .await
+                .unwrap();
+            assert_eq!(manifest.as_deref(), Some(&b"{}"[..]));
+
+            let source = FileSource::directory(dir.path());
+            let endpoint = "https://example.com/linux/x86_64/1.0.0".parse().unwrap();
+            let manifest = source.fetch_manifest(&endpoint, &headers).await.unwrap();
+            assert_eq!(manifest.as_deref(), Some(&b"{}"[..]));
+
+            let url = "https://example.com/releases/app%201.1.0.AppImage"
+                .parse()
+                .unwrap();
+            let mut package = source.download(&url, &headers, 0).await.unwrap();
+            let mut bytes = Vec::new();
+            while let Some(chunk) = package.chunks.next().await {
+                bytes.extend(chunk.unwrap());
+            }
+            assert_eq!(bytes, b"package");
+
+            let escaping = "https://example.com/releases/..%2Fsecret".parse().unwrap();
+            assert!(source.download(&escaping, &headers, 0).await.is_err());
+        });
+    }
+}
@@ -5,22 +5,19 @@
 use std::{
     collections::HashMap,
     ffi::{OsStr, OsString},
-    io::Cursor,
+    io::{Cursor, Read, Seek, Write},
     path::{Path, PathBuf},
     str::FromStr,
-    sync::Arc,
+    sync::{Arc, OnceLock},
     time::Duration,
 };
 
 use base64::Engine;
 use futures_util::StreamExt;
 use http::HeaderName;
-use minisign_verify::{PublicKey, Signature};
+use minisign_verify::{PublicKey, Signature, StreamVerifier};
 use percent_encoding::{AsciiSet, CONTROLS};
-use reqwest::{
-    header::{HeaderMap, HeaderValue},
-    ClientBuilder, StatusCode,
-};
+use reqwest::header::{HeaderMap, HeaderValue};
 use semver::Version;
 use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize};
 use tauri::{utils::platform::current_exe, Resource};
@@ -28,11 +25,14 @@ use time::OffsetDateTime;
 use url::Url;
 
 use crate::{
-    error::{Error, Result},
+    error::{EndpointError, EndpointErrorKind, Error, Result},
+    skip::SkipState,
+    source::{DefaultSource, FileSource, UpdateSource},
     Config,
 };
 
-const UPDATER_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
+const DOWNLOAD_MAX_RETRIES: u32 = 3;
+const DOWNLOAD_RETRY_DELAY: Duration = Duration::from_secs(2);
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ReleaseManifestPlatform {
@@ -40,6 +40,44 @@ pub struct ReleaseManifestPlatform {
     pub url: Url,
     /// Signature for the platform
     pub signature: String,
+    /// Mirror URLs tried in order if the package can't be downloaded from [`Self::url`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<Url>,
+    /// Hex encoded SHA-256 checksum of the package, validated before the signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// Binary patches that can be applied to previous versions instead of downloading the full package
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patches: Vec<ReleaseManifestPatch>,
+}
+
+/// A binary patch that updates the executable of a specific previous version to the announced version.
+///
+/// Patches are zstd frames compressed with the previous executable as reference,
+/// e.g. `zstd --patch-from=<old executable> <new executable>`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ReleaseManifestPatch {
+    /// Version the patch applies to
+    #[serde(deserialize_with = "parse_version")]
+    pub from: Version,
+    /// Download URL for the patch
+    pub url: Url,
+    /// Signature of the executable resulting from applying the patch
+    pub signature: String,
+    /// Hex encoded SHA-256 of the executable resulting from applying the patch
+    pub sha256: String,
+}
+
+/// A new public key announced by the update server, signed by an already trusted key.
+///
+/// Announced keys are trusted to sign the release that announces them,
+/// so it can be signed with a new key before the app ships it in its configuration.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ReleaseManifestPubkey {
+    /// The new public key
+    pub pubkey: String,
+    /// Signature of the `pubkey` value created with a trusted key
+    pub signature: String,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -62,6 +100,15 @@ pub struct RemoteRelease {
     pub notes: Option<String>,
     /// Release date.
     pub pub_date: Option<OffsetDateTime>,
+    /// New public keys announced by the release.
+    pub pubkeys: Vec<ReleaseManifestPubkey>,
+    /// Whether the release must be installed before the app can be used.
+    pub mandatory: bool,
+    /// Minimum version of the app that is still supported,
+    /// older versions must install the release before the app can be used.
+    pub minimum_version: Option<Version>,
+    /// Versions that must not be used anymore, for example because of a critical bug.
+    pub revoked_versions: Vec<Version>,
     /// Release data.
     pub data: RemoteReleaseInner,
 }
@@ -90,6 +137,115 @@ impl RemoteRelease {
                 }),
         }
     }
+
+    /// Whether the release must be installed by an app running the given version,
+    /// either because it is marked as mandatory, the version is older than the
+    /// minimum supported version or it has been revoked.
+    pub fn is_mandatory_for(&self, version: &Version) -> bool {
+        self.mandatory
+            || self
+                .minimum_version
+                .as_ref()
+                .is_some_and(|min| version < min)
+            || self.revoked_versions.contains(version)
+    }
+
+    /// The new public keys announced by the release that are signed by a key trusted by `config`.
+    ///
+    /// Keys that can't be decoded are ignored, even when their signature is valid.
+    pub(crate) fn trusted_pubkeys(&self, config: &Config) -> Result<Vec<String>> {
+        if self.pubkeys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let public_keys = decode_pubkeys(config)?;
+        Ok(self
+            .pubkeys
+            .iter()
+            .filter(|announced| {
+                decode_pubkey(&announced.pubkey).is_ok()
+                    && verify_signature(
+                        announced.pubkey.as_bytes(),
+                        &announced.signature,
+                        &public_keys,
+                    )
+                    .is_ok()
+            })
+            .map(|announced| announced.pubkey.clone())
+            .collect())
+    }
+
+    /// The release's download mirror URLs for the given target.
+    pub fn mirrors(&self, target: &str) -> Result<&[Url]> {
+        self.platform(target).map(|p| p.mirrors.as_slice())
+    }
+
+    /// The release's SHA-256 checksum for the given target.
+    pub fn sha256(&self, target: &str) -> Result<Option<&String>> {
+        self.platform(target).map(|p| p.sha256.as_ref())
+    }
+
+    /// The release's binary patch from the given version for the given target.
+    pub fn patch(&self, target: &str, from: &Version) -> Option<&ReleaseManifestPatch> {
+        self.platform(target)
+            .ok()?
+            .patches
+            .iter()
+            .find(|patch| &patch.from == from)
+    }
+
+    fn platform(&self, target: &str) -> Result<&ReleaseManifestPlatform> {
+        match self.data {
+            RemoteReleaseInner::Dynamic(ref platform) => Ok(platform),
+            RemoteReleaseInner::Static { ref platforms } => platforms
+                .get(target)
+                .ok_or_else(|| Error::TargetNotFound(target.to_string())),
+        }
+    }
+}
+
+/// The `platforms` key of `release` for this installation, e.g. `linux-x86_64-deb`
+/// when the app was installed from a Debian package, falling back to `json_target`.
+fn release_target(
+    release: &RemoteRelease,
+    json_target: &str,
+    bundle: Option<LinuxBundle>,
+) -> Result<String> {
+    if let (Some(bundle), RemoteReleaseInner::Static { platforms }) = (bundle, &release.data) {
+        let target = format!("{json_target}-{bundle}");
+        if platforms.contains_key(&target) {
+            return Ok(target);
+        }
+        // the generic release of Linux is an AppImage, it can't update a `.deb` or `.rpm` installation
+        if bundle != LinuxBundle::AppImage {
+            return Err(Error::TargetNotFound(target));
+        }
+    }
+    Ok(json_target.to_owned())
+}
+
+/// Directory where the updater keeps its downloads.
+fn updater_dir(app_name: &str) -> Result<PathBuf> {
+    // the temp dir is shared between all users on Linux
+    #[cfg(target_os = "linux")]
+    let base = dirs::cache_dir().unwrap_or_else(std::env::temp_dir);
+    #[cfg(not(target_os = "linux"))]
+    let base = std::env::temp_dir();
+
+    let dir = base.join(format!("{app_name}-updater"));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Directory where the updater keeps the state that must survive restarts, e.g. the skipped updates,
+/// when the app data directory is unknown.
+///
+/// Unlike [`updater_dir`], it is never the temporary directory which the system may clean up.
+fn default_state_dir(app_name: &str) -> Result<PathBuf> {
+    match dirs::data_local_dir() {
+        Some(dir) => Ok(dir.join(format!("{app_name}-updater"))),
+        None => updater_dir(app_name),
+    }
 }
 
 pub type OnBeforeExit = Arc<dyn Fn() + Send + Sync + 'static>;
@@ -108,6 +264,10 @@ pub struct UpdaterBuilder {
     installer_args: Vec<OsString>,
     current_exe_args: Vec<OsString>,
     on_before_exit: Option<OnBeforeExit>,
+    keep_backup: bool,
+    source: Option<Arc<dyn UpdateSource>>,
+    include_skipped: bool,
+    state_dir: Option<PathBuf>,
 }
 
 impl UpdaterBuilder {
@@ -132,6 +292,10 @@ impl UpdaterBuilder {
             timeout: None,
             proxy: None,
             on_before_exit: None,
+            keep_backup: false,
+            source: None,
+            include_skipped: false,
+            state_dir: None,
         }
     }
 
@@ -193,6 +357,16 @@ impl UpdaterBuilder {
         self
     }
 
+    /// Sets the additional public keys trusted to sign updates, used to rotate the signing key.
+    pub fn pubkeys<I, S>(mut self, pubkeys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.pubkeys = pubkeys.into_iter().map(Into::into).collect();
+        self
+    }
+
     pub fn installer_arg<S>(mut self, arg: S) -> Self
     where
         S: Into<OsString>,
@@ -221,6 +395,24 @@ impl UpdaterBuilder {
         self
     }
 
+    /// Sets where the release manifests and the update packages are fetched from.
+    ///
+    /// Defaults to an [`crate::HttpSource`] configured with [`Self::timeout`] and [`Self::proxy`]
+    /// for `http://` and `https://` URLs, and to a [`crate::FileSource`] for `file://` URLs.
+    /// The packages of a release read from a `file://` endpoint are read from the directory
+    /// of the endpoint, see [`crate::FileSource::directory`].
+    pub fn source<S: UpdateSource + 'static>(mut self, source: S) -> Self {
+        self.source.replace(Arc::new(source));
+        self
+    }
+
+    /// Whether [`Updater::check`] returns the versions skipped or postponed by the user,
+    /// e.g. when the user explicitly checks for updates. Defaults to `false`.
+    pub fn include_skipped(mut self, include_skipped: bool) -> Self {
+        self.include_skipped = include_skipped;
+        self
+    }
+
     pub fn build(self) -> Result<Updater> {
         let endpoints = self
             .endpoints
@@ -231,6 +423,7 @@ impl UpdaterBuilder {
         };
 
         let arch = get_updater_arch().ok_or(Error::UnsupportedArch)?;
+        let custom_target = self.target.is_some();
         let (target, json_target) = if let Some(target) = self.target {
             (target.clone(), target)
         } else {
@@ -247,6 +440,11 @@ impl UpdaterBuilder {
             extract_path_from_executable(&executable_path)?
         };
 
+        let state_dir = match self.state_dir {
+            Some(state_dir) => state_dir,
+            None => default_state_dir(&self.app_name)?,
+        };
+
         Ok(Updater {
             config: self.config,
             app_name: self.app_name,
@@ -260,9 +458,14 @@ impl UpdaterBuilder {
             arch,
             target,
             json_target,
+            custom_target,
             headers: self.headers,
             extract_path,
             on_before_exit: self.on_before_exit,
+            keep_backup: self.keep_backup,
+            source: self.source,
+            include_skipped: self.include_skipped,
+            state_dir,
         })
     }
 }
@@ -277,6 +480,19 @@ impl UpdaterBuilder {
         self.current_exe_args.extend_from_slice(&args);
         self
     }
+
+    /// Keeps a backup of the current installation when installing an update,
+    /// used to roll back updates that fail to launch.
+    pub(crate) fn keep_backup(mut self, keep_backup: bool) -> Self {
+        self.keep_backup = keep_backup;
+        self
+    }
+
+    /// Sets the directory where the skipped and postponed updates are kept.
+    pub(crate) fn state_dir(mut self, state_dir: PathBuf) -> Self {
+        self.state_dir.replace(state_dir);
+        self
+    }
 }
 
 pub struct Updater {
@@ -292,9 +508,14 @@ pub struct Updater {
     target: String,
     // The value we search if the updater server returns a JSON with the `platforms` object
     json_target: String,
+    custom_target: bool,
     headers: HeaderMap,
     extract_path: PathBuf,
     on_before_exit: Option<OnBeforeExit>,
+    keep_backup: bool,
+    source: Option<Arc<dyn UpdateSource>>,
+    include_skipped: bool,
+    state_dir: PathBuf,
     #[allow(unused)]
     installer_args: Vec<OsString>,
     #[allow(unused)]
@@ -302,6 +523,22 @@ pub struct Updater {
 }
 
 impl Updater {
+    /// Forgets the versions skipped or postponed with [`Update::skip`] and [`Update::postpone`].
+    pub fn clear_skipped(&self) -> Result<()> {
+        SkipState::clear(&self.state_dir)
+    }
+
+    /// Detects how the app was installed on Linux.
+    ///
+    /// The package managers are queried on a blocking thread, off the async runtime.
+    async fn bundle(&self) -> Option<LinuxBundle> {
+        let executable = self.extract_path.clone();
+        tauri::async_runtime::spawn_blocking(move || LinuxBundle::detect(&executable))
+            .await
+            .ok()
+            .flatten()
+    }
+
     pub async fn check(&self) -> Result<Option<Update>> {
         // we want JSON only
         let mut headers = self.headers.clone();
@@ -318,8 +555,15 @@ impl Updater {
             }
         }
 
+        let source: Arc<dyn UpdateSource> = match &self.source {
+            Some(source) => source.clone(),
+            None => Arc::new(DefaultSource::new(self.timeout, self.proxy.as_ref())?),
+        };
+
         let mut remote_release: Option<RemoteRelease> = None;
-        let mut last_error: Option<Error> = None;
+        // the packages of a release read from a local directory are read from the same directory
+        let mut release_source: Option<Arc<dyn UpdateSource>> = None;
+        let mut errors = Vec::new();
         for url in &self.endpoints {
             // replace {{current_version}}, {{target}} and {{arch}} in the provided URL
             // this is useful if we need to query example
@@ -346,70 +590,83 @@ impl Updater {
                 .replace("{{arch}}", self.arch)
                 .parse()?;
 
-            let mut request = ClientBuilder::new().user_agent(UPDATER_USER_AGENT);
-            if let Some(timeout) = self.timeout {
-                request = request.timeout(timeout);
-            }
-            if let Some(ref proxy) = self.proxy {
-                let proxy = reqwest::Proxy::all(proxy.as_str())?;
-                request = request.proxy(proxy);
-            }
-            let response = request
-                .build()?
-                .get(url)
-                .headers(headers.clone())
-                .send()
-                .await;
-
-            if let Ok(res) = response {
-                if res.status().is_success() {
-                    // no updates found!
-                    if StatusCode::NO_CONTENT == res.status() {
-                        return Ok(None);
-                    };
+            let body = match source.fetch_manifest(&url, &headers).await {
+                Ok(Some(body)) => body,
+                // no updates found!
+                Ok(None) => return Ok(None),
+                Err(kind) => {
+                    errors.push(EndpointError::new(url, kind));
+                    continue;
+                }
+            };
 
-                    match serde_json::from_value::<RemoteRelease>(res.json().await?)
-                        .map_err(Into::into)
-                    {
-                        Ok(release) => {
-                            last_error = None;
-                            remote_release = Some(release);
-                            // we found a relase, break the loop
-                            break;
-                        }
-                        Err(err) => last_error = Some(err),
+            match serde_json::from_slice::<RemoteRelease>(&body) {
+                Ok(release) => {
+                    if self.source.is_none() && url.scheme() == "file" {
+                        release_source = FileSource::release_root(&url)
+                            .map(|root| Arc::new(FileSource::directory(root)) as _);
                     }
+                    remote_release = Some(release);
+                    // we found a relase, break the loop
+                    break;
                 }
+                Err(err) => errors.push(EndpointError::new(url, EndpointErrorKind::parse(err))),
             }
         }
 
-        // Last error is cleaned on success.
-        // Shouldn't be triggered if we had a successfull call
-        if let Some(error) = last_error {
-            return Err(error);
-        }
-
         // Extracted remote metadata
-        let release = remote_release.ok_or(Error::ReleaseNotFound)?;
+        let release = remote_release.ok_or(Error::EndpointsFailed(errors))?;
 
         let should_update = match self.version_comparator.as_ref() {
             Some(comparator) => comparator(self.current_version.clone(), release.clone()),
             None => release.version > self.current_version,
         };
+        // a revoked version must be replaced even if the release is older, e.g. a rollback
+        let revoked = release.revoked_versions.contains(&self.current_version)
+            && release.version != self.current_version;
+        let should_update = should_update || revoked;
+
+        let mandatory = release.is_mandatory_for(&self.current_version);
+        // mandatory updates can't be skipped
+        let should_update = should_update
+            && (self.include_skipped
+                || mandatory
+                || !SkipState::read(&self.state_dir)?
+                    .ignores(&release.version, OffsetDateTime::now_utc()));
 
         let update = if should_update {
+            let bundle = self.bundle().await;
+            let json_target = if self.custom_target {
+                self.json_target.clone()
+            } else {
+                release_target(&release, &self.json_target, bundle)?
+            };
+            let mut config = self.config.clone();
+            config
+                .pubkeys
+                .extend(release.trusted_pubkeys(&self.config)?);
+
             Some(Update {
-                config: self.config.clone(),
+                config,
+                mandatory,
+                minimum_version: release.minimum_version.as_ref().map(ToString::to_string),
                 on_before_exit: self.on_before_exit.clone(),
                 app_name: self.app_name.clone(),
                 current_version: self.current_version.to_string(),
                 target: self.target.clone(),
                 extract_path: self.extract_path.clone(),
+                bundle,
+                keep_backup: self.keep_backup,
+                state_dir: self.state_dir.clone(),
+                source: release_source.or_else(|| self.source.clone()),
                 version: release.version.to_string(),
                 date: release.pub_date,
-                download_url: release.download_url(&self.json_target)?.to_owned(),
+                download_url: release.download_url(&json_target)?.to_owned(),
                 body: release.notes.clone(),
-                signature: release.signature(&self.json_target)?.to_owned(),
+                signature: release.signature(&json_target)?.to_owned(),
+                mirrors: release.mirrors(&json_target)?.to_vec(),
+                sha256: release.sha256(&json_target)?.cloned(),
+                patch: release.patch(&json_target, &self.current_version).cloned(),
                 timeout: self.timeout,
                 proxy: self.proxy.clone(),
                 headers: self.headers.clone(),
@@ -437,12 +694,23 @@ pub struct Update {
     pub version: String,
     /// Update publish date
     pub date: Option<OffsetDateTime>,
+    /// Whether the update must be installed before the app can be used,
+    /// see [`RemoteRelease::is_mandatory_for`]
+    pub mandatory: bool,
+    /// Minimum version of the app that is still supported
+    pub minimum_version: Option<String>,
     /// Target
     pub target: String,
     /// Download URL announced
     pub download_url: Url,
     /// Signature announced
     pub signature: String,
+    /// Download mirror URLs announced
+    pub mirrors: Vec<Url>,
+    /// SHA-256 checksum announced
+    pub sha256: Option<String>,
+    /// Binary patch announced for the current version
+    pub patch: Option<ReleaseManifestPatch>,
     /// Request timeout
     pub timeout: Option<Duration>,
     /// Request proxy
@@ -452,6 +720,16 @@ pub struct Update {
     /// Extract path
     #[allow(unused)]
     extract_path: PathBuf,
+    /// How the app was installed on Linux
+    #[allow(unused)]
+    bundle: Option<LinuxBundle>,
+    /// Whether to keep a backup of the current installation,
+    /// see [`crate::Builder::rollback_after_failed_launches`]
+    #[allow(unused)]
+    keep_backup: bool,
+    /// Directory of the skipped and postponed updates
+    state_dir: PathBuf,
+    source: Option<Arc<dyn UpdateSource>>,
     /// App name, used for creating named tempfiles on Windows
     #[allow(unused)]
     app_name: String,
@@ -464,14 +742,245 @@ pub struct Update {
 impl Resource for Update {}
 
 impl Update {
+    /// Skips this update, [`Updater::check`] ignores it until a newer version is released.
+    ///
+    /// Mandatory updates are never skipped, see [`Update::mandatory`].
+    pub fn skip(&self) -> Result<()> {
+        SkipState::skip(&self.state_dir, self.version.parse()?)
+    }
+
+    /// Postpones this update, [`Updater::check`] ignores it for `duration`
+    /// unless a newer version is released.
+    ///
+    /// Mandatory updates are never postponed, see [`Update::mandatory`].
+    pub fn postpone(&self, duration: Duration) -> Result<()> {
+        SkipState::postpone(&self.state_dir, self.version.parse()?, duration)
+    }
+
     /// Downloads the updater package, verifies it then return it as bytes.
     ///
     /// Use [`Update::install`] to install it
     pub async fn download<C: FnMut(usize, Option<u64>), D: FnOnce()>(
         &self,
-        mut on_chunk: C,
+        on_chunk: C,
         on_download_finish: D,
     ) -> Result<Vec<u8>> {
+        let path = self.download_to_file(on_chunk, on_download_finish).await?;
+        let bytes = std::fs::read(&path)?;
+        let _ = std::fs::remove_file(&path);
+        Ok(bytes)
+    }
+
+    /// Downloads the updater package to disk, verifies it then return its path.
+    ///
+    /// The package is streamed to a file instead of being kept in memory and its signature
+    /// is verified while it is downloaded. If the download is interrupted, it is retried
+    /// and resumed from the bytes already written to disk when the [`UpdateSource`] supports it,
+    /// e.g. using an HTTP `Range` request, this also applies to subsequent calls after this
+    /// function returned an error.
+    /// If the package can't be downloaded from [`Update::download_url`],
+    /// the [`Update::mirrors`] are tried in order. A package that was already downloaded
+    /// and is still valid is returned without downloading it again.
+    ///
+    /// Use [`Update::install_from_file`] to install it
+    pub async fn download_to_file<C: FnMut(usize, Option<u64>), D: FnOnce()>(
+        &self,
+        mut on_chunk: C,
+        on_download_finish: D,
+    ) -> Result<PathBuf> {
+        let source = self.source()?;
+
+        let download_dir = updater_dir(&self.app_name)?;
+        let path = download_dir.join(self.download_file_name());
+
+        // the background checker and the app may download the same update at once,
+        // the second one waits for the first one then uses its package
+        let _download_guard = lock_download(&path).await;
+
+        let public_keys = decode_pubkeys(&self.config)?;
+        let signature = Signature::decode(&base64_to_string(&self.signature)?)?;
+
+        // the package might have already been downloaded, e.g. by the background checker
+        if path.is_file() {
+            if self.verify_downloaded(&path, &public_keys, &signature) {
+                let len = std::fs::metadata(&path)?.len();
+                on_chunk(len as usize, Some(len));
+                on_download_finish();
+                return Ok(path);
+            }
+            let _ = std::fs::remove_file(&path);
+        }
+
+        // patches can only be applied to a single file executable (the Linux AppImage),
+        // if anything goes wrong we fallback to downloading the full package
+        #[cfg(all(feature = "delta", target_os = "linux"))]
+        if let Some(patch) = &self.patch {
+            if self.bundle == Some(LinuxBundle::AppImage) && self.extract_path.is_file() {
+                // the progress of the patch is only reported once it applied successfully,
+                // so the progress of the full package starts from zero if we fallback to it
+                let mut downloaded = 0;
+                let mut total = None;
+                match self
+                    .download_patched(source.as_ref(), patch, &path, &mut |len, content_length| {
+                        downloaded += len;
+                        total = content_length;
+                    })
+                    .await
+                {
+                    Ok(()) => {
+                        on_chunk(downloaded, total);
+                        on_download_finish();
+                        return Ok(path);
+                    }
+                    Err(err) => log::warn!(
+                        "failed to apply the update patch from {}, downloading the full package instead: {err}",
+                        patch.url
+                    ),
+                }
+            }
+        }
+
+        let partial_path = download_dir.join(format!("{}.part", self.download_file_name()));
+
+        let mut mirrors = self.mirrors.iter();
+        let mut url = &self.download_url;
+        let verifier = loop {
+            match self
+                .download_with_retries(
+                    source.as_ref(),
+                    url,
+                    &partial_path,
+                    &public_keys,
+                    Some(&signature),
+                    &mut on_chunk,
+                )
+                .await
+            {
+                Ok(verifier) => break verifier,
+                // the server is unreachable or failed to respond, let's try the next mirror
+                Err(err @ (Error::Reqwest(_) | Error::Network(_))) => match mirrors.next() {
+                    Some(mirror) => url = mirror,
+                    None => return Err(err),
+                },
+                Err(err) => return Err(err),
+            }
+        };
+
+        on_download_finish();
+
+        if let Err(err) = verifier.verify(
+            &partial_path,
+            self.sha256.as_deref(),
+            &self.signature,
+            &public_keys,
+        ) {
+            // the downloaded file can't be trusted, do not resume from it next time
+            let _ = std::fs::remove_file(&partial_path);
+            return Err(err);
+        }
+
+        std::fs::rename(&partial_path, &path)?;
+
+        Ok(path)
+    }
+
+    /// Whether the file at `path` is a valid package for this update,
+    /// either the full package or the executable patched by [`Self::download_patched`].
+    fn verify_downloaded(
+        &self,
+        path: &Path,
+        public_keys: &[PublicKey],
+        signature: &Signature,
+    ) -> bool {
+        let mut verifier = DownloadVerifier::new(public_keys, Some(signature));
+        let verified = verifier.update_from_file(path).is_ok()
+            && verifier
+                .verify(path, self.sha256.as_deref(), &self.signature, public_keys)
+                .is_ok();
+
+        #[cfg(all(feature = "delta", target_os = "linux"))]
+        if !verified {
+            if let Some(patch) = &self.patch {
+                return verify_file(path, &patch.signature, &self.config, &patch.sha256).is_ok();
+            }
+        }
+
+        verified
+    }
+
+    /// Downloads the binary patch for the current version and applies it to the executable,
+    /// writing the patched executable to `path` once its checksum and signature are verified.
+    #[cfg(all(feature = "delta", target_os = "linux"))]
+    async fn download_patched<C: FnMut(usize, Option<u64>)>(
+        &self,
+        source: &dyn UpdateSource,
+        patch: &ReleaseManifestPatch,
+        path: &Path,
+        on_chunk: &mut C,
+    ) -> Result<()> {
+        let mut patch_path = path.as_os_str().to_os_string();
+        patch_path.push(".patch");
+        let patch_path = PathBuf::from(patch_path);
+
+        // the patch itself is not signed, we verify the executable resulting from it instead
+        self.download_with_retries(source, &patch.url, &patch_path, &[], None, on_chunk)
+            .await?;
+
+        let result = apply_patch(&self.extract_path, &patch_path, path)
+            .and_then(|_| verify_file(path, &patch.signature, &self.config, &patch.sha256));
+
+        let _ = std::fs::remove_file(&patch_path);
+        if result.is_err() {
+            let _ = std::fs::remove_file(path);
+        }
+
+        result
+    }
+
+    /// Downloads `url` into `path`, resuming the download if a network error happens.
+    ///
+    /// Returns the verifier fed with the downloaded bytes.
+    async fn download_with_retries<'a, C: FnMut(usize, Option<u64>)>(
+        &self,
+        source: &dyn UpdateSource,
+        url: &Url,
+        path: &Path,
+        public_keys: &'a [PublicKey],
+        signature: Option<&'a Signature>,
+        on_chunk: &mut C,
+    ) -> Result<DownloadVerifier<'a>> {
+        let mut retries = 0;
+        let mut reported = 0;
+        loop {
+            let mut verifier = DownloadVerifier::new(public_keys, signature);
+            match self
+                .download_range(source, url, path, &mut verifier, &mut reported, on_chunk)
+                .await
+            {
+                Ok(()) => return Ok(verifier),
+                // network errors while the request is in flight, let's resume the download
+                Err(Error::Reqwest(_)) if retries < DOWNLOAD_MAX_RETRIES => {
+                    retries += 1;
+                    tokio::time::sleep(DOWNLOAD_RETRY_DELAY * retries).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Downloads the remaining bytes of `url` into `path`,
+    /// resuming from its current length if the source supports it.
+    ///
+    /// `reported` is the number of bytes already reported to `on_chunk` by a previous attempt.
+    async fn download_range<C: FnMut(usize, Option<u64>)>(
+        &self,
+        source: &dyn UpdateSource,
+        url: &Url,
+        path: &Path,
+        verifier: &mut DownloadVerifier<'_>,
+        reported: &mut u64,
+        on_chunk: &mut C,
+    ) -> Result<()> {
         // set our headers
         let mut headers = self.headers.clone();
         headers.insert(
@@ -479,52 +988,58 @@ impl Update {
             HeaderValue::from_str("application/octet-stream").unwrap(),
         );
 
-        let mut request = ClientBuilder::new().user_agent(UPDATER_USER_AGENT);
-        if let Some(timeout) = self.timeout {
-            request = request.timeout(timeout);
-        }
-        if let Some(ref proxy) = self.proxy {
-            let proxy = reqwest::Proxy::all(proxy.as_str())?;
-            request = request.proxy(proxy);
-        }
-        let response = request
-            .build()?
-            .get(self.download_url.clone())
-            .headers(headers)
-            .send()
-            .await?;
+        let offset = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let mut package = source.download(url, &headers, offset).await?;
 
-        if !response.status().is_success() {
-            return Err(Error::Network(format!(
-                "Download request failed with status: {}",
-                response.status()
-            )));
-        }
-
-        let content_length: Option<u64> = response
-            .headers()
-            .get("Content-Length")
-            .and_then(|value| value.to_str().ok())
-            .and_then(|value| value.parse().ok());
+        let (mut file, content_length) = if package.resumed {
+            let content_length = package.content_length.map(|l| l + offset);
+            verifier.update_from_file(path)?;
+            on_chunk(offset.saturating_sub(*reported) as usize, content_length);
+            *reported = offset;
 
-        let mut buffer = Vec::new();
+            let file = std::fs::OpenOptions::new().append(true).open(path)?;
+            (file, content_length)
+        } else {
+            (std::fs::File::create(path)?, package.content_length)
+        };
 
-        let mut stream = response.bytes_stream();
-        while let Some(chunk) = stream.next().await {
+        while let Some(chunk) = package.chunks.next().await {
             let chunk = chunk?;
+            file.write_all(&chunk)?;
+            verifier.update(&chunk);
+            *reported += chunk.len() as u64;
             on_chunk(chunk.len(), content_length);
-            buffer.extend(chunk);
         }
-        on_download_finish();
+        file.sync_all()?;
 
-        verify_signature(&buffer, &self.signature, &self.config.pubkey)?;
+        Ok(())
+    }
 
-        Ok(buffer)
+    fn source(&self) -> Result<Arc<dyn UpdateSource>> {
+        match &self.source {
+            Some(source) => Ok(source.clone()),
+            None => Ok(Arc::new(DefaultSource::new(
+                self.timeout,
+                self.proxy.as_ref(),
+            )?)),
+        }
+    }
+
+    /// File name of the updater package in the download directory.
+    fn download_file_name(&self) -> String {
+        format!("{}-{}-{}", self.app_name, self.version, self.target)
     }
 
     /// Installs the updater package downloaded by [`Update::download`]
     pub fn install(&self, bytes: impl AsRef<[u8]>) -> Result<()> {
-        self.install_inner(bytes.as_ref())
+        self.install_inner(Package::Bytes(bytes.as_ref()))
+    }
+
+    /// Installs the updater package downloaded by [`Update::download_to_file`]
+    ///
+    /// The package is read from the disk while it is installed, it is never loaded in memory as a whole.
+    pub fn install_from_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.install_inner(Package::File(path.as_ref()))
     }
 
     /// Downloads and installs the updater package
@@ -533,12 +1048,69 @@ impl Update {
         on_chunk: C,
         on_download_finish: D,
     ) -> Result<()> {
-        let bytes = self.download(on_chunk, on_download_finish).await?;
-        self.install(bytes)
+        let path = self.download_to_file(on_chunk, on_download_finish).await?;
+        let result = self.install_from_file(&path);
+        let _ = std::fs::remove_file(&path);
+        result
     }
 
     #[cfg(mobile)]
-    fn install_inner(&self, _bytes: &[u8]) -> Result<()> {
+    fn install_inner(&self, _package: Package<'_>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Locks the download of the package at `path` for the whole process.
+async fn lock_download(path: &Path) -> tokio::sync::OwnedMutexGuard<()> {
+    type DownloadLocks = std::sync::Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>;
+    static DOWNLOAD_LOCKS: OnceLock<DownloadLocks> = OnceLock::new();
+
+    let lock = DOWNLOAD_LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .entry(path.to_path_buf())
+        .or_default()
+        .clone();
+    lock.lock_owned().await
+}
+
+/// Number of bytes at the start of a package used to detect its format.
+const PACKAGE_HEAD_SIZE: u64 = 8192;
+
+trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// An updater package to install, either in memory or downloaded to disk.
+#[derive(Clone, Copy)]
+enum Package<'a> {
+    Bytes(&'a [u8]),
+    File(&'a Path),
+}
+
+impl<'a> Package<'a> {
+    #[cfg(desktop)]
+    fn reader(self) -> Result<Box<dyn ReadSeek + 'a>> {
+        Ok(match self {
+            Self::Bytes(bytes) => Box::new(Cursor::new(bytes)),
+            Self::File(path) => Box::new(std::fs::File::open(path)?),
+        })
+    }
+
+    /// The start of the package, used to detect its format.
+    #[cfg(all(desktop, not(target_os = "macos")))]
+    fn head(self) -> Result<Vec<u8>> {
+        let mut head = Vec::new();
+        self.reader()?
+            .take(PACKAGE_HEAD_SIZE)
+            .read_to_end(&mut head)?;
+        Ok(head)
+    }
+
+    #[cfg(all(desktop, not(target_os = "macos")))]
+    fn write_to(self, writer: &mut impl Write) -> Result<()> {
+        std::io::copy(&mut self.reader()?, writer)?;
         Ok(())
     }
 }
@@ -592,14 +1164,14 @@ impl Update {
     /// ├── [AppName]_[version]_x64-setup.exe.zip          # ZIP generated by tauri-bundler
     /// │   └──[AppName]_[version]_x64-setup.exe           # NSIS installer
     /// └── ...
-    fn install_inner(&self, bytes: &[u8]) -> Result<()> {
+    fn install_inner(&self, package: Package<'_>) -> Result<()> {
         use std::iter::once;
         use windows_sys::{
             w,
             Win32::UI::{Shell::ShellExecuteW, WindowsAndMessaging::SW_SHOW},
         };
 
-        let updater_type = self.extract(bytes)?;
+        let updater_type = self.extract(package)?;
 
         let install_mode = self.config.install_mode();
         let current_args = &self.current_exe_args()[1..];
@@ -678,13 +1250,15 @@ impl Update {
             .collect::<Vec<_>>()
     }
 
-    fn extract(&self, bytes: &[u8]) -> Result<WindowsUpdaterType> {
+    fn extract(&self, package: Package<'_>) -> Result<WindowsUpdaterType> {
+        let head = package.head()?;
+
         #[cfg(feature = "zip")]
-        if infer::archive::is_zip(bytes) {
-            return self.extract_zip(bytes);
+        if infer::archive::is_zip(&head) {
+            return self.extract_zip(package);
         }
 
-        self.extract_exe(bytes)
+        self.extract_exe(package, &head)
     }
 
     fn make_temp_dir(&self) -> Result<PathBuf> {
@@ -695,11 +1269,10 @@ impl Update {
     }
 
     #[cfg(feature = "zip")]
-    fn extract_zip(&self, bytes: &[u8]) -> Result<WindowsUpdaterType> {
+    fn extract_zip(&self, package: Package<'_>) -> Result<WindowsUpdaterType> {
         let temp_dir = self.make_temp_dir()?;
 
-        let archive = Cursor::new(bytes);
-        let mut extractor = zip::ZipArchive::new(archive)?;
+        let mut extractor = zip::ZipArchive::new(package.reader()?)?;
         extractor.extract(&temp_dir)?;
 
         let paths = std::fs::read_dir(&temp_dir)?;
@@ -716,12 +1289,12 @@ impl Update {
         Err(crate::Error::BinaryNotFoundInArchive)
     }
 
-    fn extract_exe(&self, bytes: &[u8]) -> Result<WindowsUpdaterType> {
-        if infer::app::is_exe(bytes) {
-            let (path, temp) = self.write_to_temp(bytes, ".exe")?;
+    fn extract_exe(&self, package: Package<'_>, head: &[u8]) -> Result<WindowsUpdaterType> {
+        if infer::app::is_exe(head) {
+            let (path, temp) = self.write_to_temp(package, ".exe")?;
             Ok(WindowsUpdaterType::nsis(path, temp))
-        } else if infer::archive::is_msi(bytes) {
-            let (path, temp) = self.write_to_temp(bytes, ".msi")?;
+        } else if infer::archive::is_msi(head) {
+            let (path, temp) = self.write_to_temp(package, ".msi")?;
             Ok(WindowsUpdaterType::msi(path, temp))
         } else {
             Err(crate::Error::InvalidUpdaterFormat)
@@ -730,18 +1303,16 @@ impl Update {
 
     fn write_to_temp(
         &self,
-        bytes: &[u8],
+        package: Package<'_>,
         ext: &str,
     ) -> Result<(PathBuf, Option<tempfile::TempPath>)> {
-        use std::io::Write;
-
         let temp_dir = self.make_temp_dir()?;
         let mut temp_file = tempfile::Builder::new()
             .prefix(&format!("{}-{}-installer", self.app_name, self.version))
             .suffix(ext)
             .rand_bytes(0)
             .tempfile_in(temp_dir)?;
-        temp_file.write_all(bytes)?;
+        package.write_to(&mut temp_file)?;
 
         let temp = temp_file.into_temp_path();
         Ok((temp.to_path_buf(), Some(temp)))
@@ -765,7 +1336,55 @@ impl Update {
     /// We should have an AppImage already installed to be able to copy and install
     /// the extract_path is the current AppImage path
     /// tmp_dir is where our new AppImage is found
-    fn install_inner(&self, bytes: &[u8]) -> Result<()> {
+    ///
+    /// Apps installed from a `.deb` or `.rpm` package are updated by installing
+    /// the new package with `pkexec`, see [`Self::install_package`].
+    fn install_inner(&self, package: Package<'_>) -> Result<()> {
+        match self.bundle {
+            Some(bundle @ (LinuxBundle::Deb | LinuxBundle::Rpm)) => {
+                self.install_package(bundle, package)
+            }
+            _ => self.install_appimage(package),
+        }
+    }
+
+    /// ### Expected structure:
+    /// ├── [AppName]_[version]_amd64.deb              # Debian package
+    /// ├── [AppName]-[version]-1.x86_64.rpm           # RPM package
+    /// └── ...
+    ///
+    /// The package was verified when it was downloaded, it is then installed
+    /// by the package manager as root through `pkexec`, prompting the user for authentication.
+    fn install_package(&self, bundle: LinuxBundle, package: Package<'_>) -> Result<()> {
+        use std::process::Command;
+
+        let head = package.head()?;
+        let (extension, command): (_, &[&str]) = match bundle {
+            LinuxBundle::Deb if infer::archive::is_deb(&head) => (".deb", &["dpkg", "-i"]),
+            LinuxBundle::Rpm if infer::archive::is_rpm(&head) => (".rpm", &["rpm", "-U"]),
+            _ => return Err(Error::InvalidUpdaterFormat),
+        };
+
+        let mut package_file = tempfile::Builder::new()
+            .prefix(&format!("{}-{}-", self.app_name, self.version))
+            .suffix(extension)
+            .tempfile()?;
+        package.write_to(&mut package_file)?;
+        package_file.flush()?;
+
+        let status = Command::new("pkexec")
+            .args(command)
+            .arg(package_file.path())
+            .status()?;
+
+        if !status.success() {
+            return Err(Error::PackageInstallFailed(status.to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn install_appimage(&self, package: Package<'_>) -> Result<()> {
         use std::os::unix::fs::{MetadataExt, PermissionsExt};
         let extract_path_metadata = self.extract_path.metadata()?;
 
@@ -795,11 +1414,10 @@ impl Update {
                     std::fs::rename(&self.extract_path, tmp_app_image)?;
 
                     #[cfg(feature = "zip")]
-                    if infer::archive::is_gz(bytes) {
-                        // extract the buffer to the tmp_dir
+                    if infer::archive::is_gz(&package.head()?) {
+                        // extract the package to the tmp_dir
                         // we extract our signed archive into our final directory without any temp file
-                        let archive = Cursor::new(bytes);
-                        let decoder = flate2::read::GzDecoder::new(archive);
+                        let decoder = flate2::read::GzDecoder::new(package.reader()?);
                         let mut archive = tar::Archive::new(decoder);
                         for mut entry in archive.entries()?.flatten() {
                             if let Ok(path) = entry.path() {
@@ -810,7 +1428,7 @@ impl Update {
                                         return Err(err.into());
                                     }
                                     // early finish we have everything we need here
-                                    return Ok(());
+                                    return self.keep_backup(tmp_app_image);
                                 }
                             }
                         }
@@ -819,15 +1437,19 @@ impl Update {
                         return Err(Error::BinaryNotFoundInArchive);
                     }
 
-                    return match std::fs::write(&self.extract_path, bytes)
-                        .and_then(|_| std::fs::set_permissions(&self.extract_path, permissions))
-                    {
+                    return match std::fs::File::create(&self.extract_path)
+                        .map_err(Into::into)
+                        .and_then(|mut file| package.write_to(&mut file))
+                        .and_then(|_| {
+                            std::fs::set_permissions(&self.extract_path, permissions)
+                                .map_err(Into::into)
+                        }) {
                         Err(err) => {
                             // if something went wrong during the extraction, we should restore previous app
                             std::fs::rename(tmp_app_image, &self.extract_path)?;
-                            Err(err.into())
+                            Err(err)
                         }
-                        Ok(_) => Ok(()),
+                        Ok(_) => self.keep_backup(tmp_app_image),
                     };
                 }
             }
@@ -835,6 +1457,30 @@ impl Update {
 
         Err(Error::TempDirNotOnSameMountPoint)
     }
+
+    /// Moves the backup of the previous AppImage out of the temporary directory
+    /// and waits for the launch of the update to be confirmed.
+    fn keep_backup(&self, tmp_app_image: &Path) -> Result<()> {
+        use crate::rollback::{move_file, PendingUpdate};
+
+        if !self.keep_backup {
+            return Ok(());
+        }
+
+        let dir = &self.state_dir;
+        std::fs::create_dir_all(dir)?;
+        let backup = PendingUpdate::backup_path(dir);
+        move_file(tmp_app_image, &backup)?;
+        PendingUpdate {
+            target: self.extract_path.clone(),
+            backup,
+            previous_version: self.current_version.clone(),
+            version: self.version.clone(),
+            launches: 0,
+            rolled_back: false,
+        }
+        .write(dir)
+    }
 }
 
 /// MacOS
@@ -846,10 +1492,9 @@ impl Update {
     /// │      └── Contents                          # Application contents...
     /// │          └── ...
     /// └── ...
-    fn install_inner(&self, bytes: &[u8]) -> Result<()> {
+    fn install_inner(&self, package: Package<'_>) -> Result<()> {
         use flate2::read::GzDecoder;
 
-        let cursor = Cursor::new(bytes);
         let mut extracted_files: Vec<PathBuf> = Vec::new();
 
         // the first file in the tar.gz will always be
@@ -861,7 +1506,7 @@ impl Update {
         // create backup of our current app
         std::fs::rename(&self.extract_path, tmp_dir.path())?;
 
-        let decoder = GzDecoder::new(cursor);
+        let decoder = GzDecoder::new(package.reader()?);
         let mut archive = tar::Archive::new(decoder);
 
         std::fs::create_dir(&self.extract_path)?;
@@ -898,6 +1543,96 @@ impl Update {
     }
 }
 
+/// How the app was installed on Linux.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(unused)]
+pub(crate) enum LinuxBundle {
+    AppImage,
+    Deb,
+    Rpm,
+}
+
+impl LinuxBundle {
+    /// Detects how `executable` was installed, `None` on other platforms.
+    ///
+    /// The package managers are only queried once per executable,
+    /// as they are queried for every available update.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    fn detect(executable: &Path) -> Option<Self> {
+        type DetectedBundles = std::sync::Mutex<HashMap<PathBuf, Option<LinuxBundle>>>;
+        static DETECTED: OnceLock<DetectedBundles> = OnceLock::new();
+
+        let detected = DETECTED.get_or_init(Default::default);
+        if let Some(bundle) = detected.lock().unwrap().get(executable) {
+            return *bundle;
+        }
+        let bundle = Self::query(executable);
+        detected
+            .lock()
+            .unwrap()
+            .insert(executable.to_path_buf(), bundle);
+        bundle
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    fn query(executable: &Path) -> Option<Self> {
+        use std::process::{Command, Stdio};
+
+        let owned_by = |program: &str, arg: &str| {
+            Command::new(program)
+                .arg(arg)
+                .arg(executable)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_ok_and(|status| status.success())
+        };
+
+        if std::env::var_os("APPIMAGE").is_some() {
+            Some(Self::AppImage)
+        } else if owned_by("dpkg", "-S") {
+            Some(Self::Deb)
+        } else if owned_by("rpm", "-qf") {
+            Some(Self::Rpm)
+        } else {
+            Some(Self::AppImage)
+        }
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    fn detect(_executable: &Path) -> Option<Self> {
+        None
+    }
+}
+
+impl std::fmt::Display for LinuxBundle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::AppImage => "appimage",
+            Self::Deb => "deb",
+            Self::Rpm => "rpm",
+        })
+    }
+}
+
 /// Gets the target string used on the updater.
 pub fn target() -> Option<String> {
     if let (Some(target), Some(arch)) = (get_updater_target(), get_updater_arch()) {
@@ -975,10 +1710,23 @@ impl<'de> Deserialize<'de> for RemoteRelease {
             version: Version,
             notes: Option<String>,
             pub_date: Option<String>,
+            #[serde(default)]
+            pubkeys: Vec<ReleaseManifestPubkey>,
+            #[serde(default)]
+            mandatory: bool,
+            #[serde(default, deserialize_with = "parse_optional_version")]
+            minimum_version: Option<Version>,
+            #[serde(default, deserialize_with = "parse_versions")]
+            revoked_versions: Vec<Version>,
             platforms: Option<HashMap<String, ReleaseManifestPlatform>>,
             // dynamic platform response
             url: Option<Url>,
             signature: Option<String>,
+            #[serde(default)]
+            mirrors: Vec<Url>,
+            sha256: Option<String>,
+            #[serde(default)]
+            patches: Vec<ReleaseManifestPatch>,
         }
 
         let release = InnerRemoteRelease::deserialize(deserializer)?;
@@ -996,6 +1744,10 @@ impl<'de> Deserialize<'de> for RemoteRelease {
             version: release.version,
             notes: release.notes,
             pub_date,
+            pubkeys: release.pubkeys,
+            mandatory: release.mandatory,
+            minimum_version: release.minimum_version,
+            revoked_versions: release.revoked_versions,
             data: if let Some(platforms) = release.platforms {
                 RemoteReleaseInner::Static { platforms }
             } else {
@@ -1006,6 +1758,9 @@ impl<'de> Deserialize<'de> for RemoteRelease {
                     signature: release.signature.ok_or_else(|| {
                         DeError::custom("the `signature` field was not set on the updater response")
                     })?,
+                    mirrors: release.mirrors,
+                    sha256: release.sha256,
+                    patches: release.patches,
                 })
             },
         })
@@ -1021,17 +1776,199 @@ where
     Version::from_str(str.trim_start_matches('v')).map_err(serde::de::Error::custom)
 }
 
-// Validate signature
-fn verify_signature(data: &[u8], release_signature: &str, pub_key: &str) -> Result<bool> {
+fn parse_optional_version<'de, D>(deserializer: D) -> std::result::Result<Option<Version>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|str| Version::from_str(str.trim_start_matches('v')))
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+fn parse_versions<'de, D>(deserializer: D) -> std::result::Result<Vec<Version>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|str| Version::from_str(str.trim_start_matches('v')))
+        .collect::<std::result::Result<_, _>>()
+        .map_err(serde::de::Error::custom)
+}
+
+/// Decodes the public keys trusted to sign updates.
+fn decode_pubkey(pub_key: &str) -> Result<PublicKey> {
     // we need to convert the pub key
     let pub_key_decoded = base64_to_string(pub_key)?;
-    let public_key = PublicKey::decode(&pub_key_decoded)?;
+    Ok(PublicKey::decode(&pub_key_decoded)?)
+}
+
+fn decode_pubkeys(config: &Config) -> Result<Vec<PublicKey>> {
+    let public_keys = config
+        .trusted_pubkeys()
+        .map(decode_pubkey)
+        .collect::<Result<Vec<_>>>()?;
+
+    if public_keys.is_empty() {
+        return Err(Error::EmptyPubkeys);
+    }
+
+    Ok(public_keys)
+}
+
+// Validate signature against any of the trusted keys
+fn verify_signature(
+    data: &[u8],
+    release_signature: &str,
+    public_keys: &[PublicKey],
+) -> Result<bool> {
     let signature_base64_decoded = base64_to_string(release_signature)?;
     let signature = Signature::decode(&signature_base64_decoded)?;
 
-    // Validate signature or bail out
-    public_key.verify(data, &signature, true)?;
-    Ok(true)
+    // Validate signature or bail out with the error of the last key
+    let mut error = minisign_verify::Error::UnexpectedKeyId;
+    for public_key in public_keys {
+        match public_key.verify(data, &signature, true) {
+            Ok(()) => return Ok(true),
+            Err(e) => error = e,
+        }
+    }
+    Err(error.into())
+}
+
+/// Creates an incremental verifier using the trusted key the signature was created with.
+///
+/// Returns `None` if no key matches or if the signature is a legacy one
+/// which can't be verified incrementally.
+fn stream_verifier<'a>(
+    public_keys: &'a [PublicKey],
+    signature: &'a Signature,
+) -> Option<StreamVerifier<'a>> {
+    public_keys
+        .iter()
+        .find_map(|public_key| public_key.verify_stream(signature).ok())
+}
+
+/// Applies a `zstd --patch-from` patch to `old`, writing the result to `output`.
+#[cfg(all(feature = "delta", target_os = "linux"))]
+fn apply_patch(old: &Path, patch: &Path, output: &Path) -> Result<()> {
+    let old = std::fs::read(old)?;
+    let patch = std::io::BufReader::new(std::fs::File::open(patch)?);
+
+    let mut decoder = zstd::stream::read::Decoder::with_ref_prefix(patch, &old)?;
+    decoder.window_log_max(patch_window_log_max(old.len()))?;
+
+    let mut output = std::fs::File::create(output)?;
+    std::io::copy(&mut decoder, &mut output)?;
+    output.sync_all()?;
+
+    Ok(())
+}
+
+/// Largest window allowed for a patch of an executable of `old_len` bytes.
+///
+/// Patches are not signed, so the window they request is capped to what referencing
+/// the whole previous executable needs instead of letting them allocate gigabytes.
+#[cfg(all(feature = "delta", target_os = "linux"))]
+fn patch_window_log_max(old_len: usize) -> u32 {
+    // the window zstd picks by default, enough for small executables
+    const MIN_WINDOW_LOG: u32 = 27;
+    let max_window_log = if cfg!(target_pointer_width = "64") {
+        31
+    } else {
+        30
+    };
+
+    let needed = (old_len as u64)
+        .saturating_mul(2)
+        .checked_next_power_of_two()
+        .map_or(u64::BITS, u64::trailing_zeros);
+    needed.clamp(MIN_WINDOW_LOG, max_window_log)
+}
+
+/// Validates the SHA-256 checksum and the signature of the file at `path`.
+#[cfg(all(feature = "delta", target_os = "linux"))]
+fn verify_file(path: &Path, release_signature: &str, config: &Config, sha256: &str) -> Result<()> {
+    let public_keys = decode_pubkeys(config)?;
+    let signature = Signature::decode(&base64_to_string(release_signature)?)?;
+
+    let mut verifier = DownloadVerifier::new(&public_keys, Some(&signature));
+    verifier.update_from_file(path)?;
+    verifier.verify(path, Some(sha256), release_signature, &public_keys)
+}
+
+/// Incrementally computes the checksum and validates the signature of a downloaded file.
+struct DownloadVerifier<'a> {
+    // legacy signatures are not prehashed and can't be verified incrementally,
+    // those are verified against the whole file once the download is done
+    signature: Option<StreamVerifier<'a>>,
+    sha256: sha2::Sha256,
+}
+
+impl<'a> DownloadVerifier<'a> {
+    fn new(public_keys: &'a [PublicKey], signature: Option<&'a Signature>) -> Self {
+        use sha2::Digest;
+
+        Self {
+            signature: signature.and_then(|signature| stream_verifier(public_keys, signature)),
+            sha256: sha2::Sha256::new(),
+        }
+    }
+
+    fn update(&mut self, buf: &[u8]) {
+        use sha2::Digest;
+
+        if let Some(verifier) = self.signature.as_mut() {
+            verifier.update(buf);
+        }
+        self.sha256.update(buf);
+    }
+
+    fn update_from_file(&mut self, path: &Path) -> Result<()> {
+        let mut file = std::fs::File::open(path)?;
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            self.update(&buffer[..read]);
+        }
+
+        Ok(())
+    }
+
+    /// Validates the checksum, if any, then the signature of the file at `path`
+    /// which contents were fed to this verifier.
+    fn verify(
+        self,
+        path: &Path,
+        sha256: Option<&str>,
+        release_signature: &str,
+        public_keys: &[PublicKey],
+    ) -> Result<()> {
+        use sha2::Digest;
+
+        if let Some(expected) = sha256 {
+            let checksum = format!("{:x}", self.sha256.finalize());
+            if !checksum.eq_ignore_ascii_case(expected) {
+                return Err(Error::ChecksumMismatch {
+                    expected: expected.into(),
+                    actual: checksum,
+                });
+            }
+        }
+
+        match self.signature {
+            Some(mut verifier) => verifier.finalize()?,
+            None => {
+                verify_signature(&std::fs::read(path)?, release_signature, public_keys)?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 fn base64_to_string(base64_string: &str) -> Result<String> {
@@ -1097,6 +2034,442 @@ fn escape_msi_property_arg(arg: impl AsRef<OsStr>) -> String {
 #[cfg(test)]
 mod tests {
 
+    #[test]
+    fn it_reports_the_error_of_each_endpoint() {
+        use std::{
+            io::{Read, Write},
+            net::TcpListener,
+        };
+
+        fn serve(response: &'static str) -> String {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = listener.local_addr().unwrap();
+            std::thread::spawn(move || {
+                let (mut stream, _) = listener.accept().unwrap();
+                let _ = stream.read(&mut [0; 1024]);
+                stream.write_all(response.as_bytes()).unwrap();
+            });
+            format!("http://{addr}")
+        }
+
+        let not_found = serve("HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\n\r\n");
+        let invalid = serve("HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\n{}");
+
+        let config: crate::Config = serde_json::from_value(serde_json::json!({
+            "endpoints": [not_found, invalid],
+            "pubkey": "",
+            "dangerousInsecureTransportProtocol": true,
+        }))
+        .unwrap();
+        let updater = super::UpdaterBuilder::new(
+            "updater-test".into(),
+            semver::Version::new(1, 0, 0),
+            config,
+        )
+        .target("test-target")
+        .build()
+        .unwrap();
+
+        let Err(crate::Error::EndpointsFailed(errors)) =
+            tauri::async_runtime::block_on(updater.check())
+        else {
+            panic!("expected every endpoint to fail");
+        };
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0].kind,
+            crate::EndpointErrorKind::Status { status: 404 }
+        ));
+        assert!(matches!(
+            errors[1].kind,
+            crate::EndpointErrorKind::Parse { .. }
+        ));
+
+        let json = serde_json::to_value(&errors[0]).unwrap();
+        assert_eq!(json["kind"], "status");
+        assert_eq!(json["status"], 404);
+    }
+
+    #[test]
+    fn it_prefers_the_release_of_the_installed_bundle() {
+        use super::{release_target, LinuxBundle, RemoteRelease};
+
+        let release: RemoteRelease = serde_json::from_value(serde_json::json!({
+            "version": "1.1.0",
+            "platforms": {
+                "linux-x86_64": { "url": "https://example.com/app.AppImage", "signature": "" },
+                "linux-x86_64-deb": { "url": "https://example.com/app.deb", "signature": "" },
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(
+            release_target(&release, "linux-x86_64", Some(LinuxBundle::Deb)).unwrap(),
+            "linux-x86_64-deb"
+        );
+
+        // no RPM release, the generic AppImage can't update an RPM installation
+        assert!(matches!(
+            release_target(&release, "linux-x86_64", Some(LinuxBundle::Rpm)),
+            Err(crate::Error::TargetNotFound(target)) if target == "linux-x86_64-rpm"
+        ));
+
+        // no `-appimage` release, fallback to the generic one
+        assert_eq!(
+            release_target(&release, "linux-x86_64", Some(LinuxBundle::AppImage)).unwrap(),
+            "linux-x86_64"
+        );
+
+        // not on Linux
+        assert_eq!(
+            release_target(&release, "linux-x86_64", None).unwrap(),
+            "linux-x86_64"
+        );
+    }
+
+    #[test]
+    fn it_parses_release_patches() {
+        use super::RemoteRelease;
+        use semver::Version;
+
+        let release: RemoteRelease = serde_json::from_value(serde_json::json!({
+            "version": "1.1.0",
+            "platforms": {
+                "linux-x86_64": {
+                    "url": "https://example.com/app_1.1.0_amd64.AppImage",
+                    "signature": "full",
+                    "patches": [{
+                        "from": "v1.0.0",
+                        "url": "https://example.com/app_1.0.0_1.1.0_amd64.AppImage.patch",
+                        "signature": "patched",
+                        "sha256": "abcdef"
+                    }]
+                }
+            }
+        }))
+        .unwrap();
+
+        let patch = release
+            .patch("linux-x86_64", &Version::new(1, 0, 0))
+            .unwrap();
+        assert_eq!(patch.signature, "patched");
+        assert!(release
+            .patch("linux-x86_64", &Version::new(0, 9, 0))
+            .is_none());
+        assert!(release
+            .patch("windows-x86_64", &Version::new(1, 0, 0))
+            .is_none());
+    }
+
+    const TEST_PUBKEY: &str = "untrusted comment: minisign public key E7620F1842B4E81F
+RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+    // signature of `test` created with the `TEST_PUBKEY` secret key
+    const TEST_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RWQf6LRCGA9i59SLOFxz6NxvASXDJeRtuZykwQepbDEGt87ig1BNpWaVWuNrm73YiIiJbq71Wi+dP9eKL8OC351vwIasSSbXxwA=
+trusted comment: timestamp:1555779966\tfile:test
+QtKMXWyYcwdpZAlPF7tE2ENJkRd1ujvKjlj1m9RtHTBnZPa5WKU5uWRs5GoP5M/VqE81QFuMKI5k/SfNQUaOAA==";
+    const OTHER_PUBKEY: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IEUwNDRGMjkwRjg2MDhCRDAKUldUUWkyRDRrUEpFNEQ4SmdwcU5PaXl6R2ZRUUNvUnhIaVkwVUltV0NMaEx6VTkrWVhpT0ZqeEEK";
+    const SIGNER_PUBKEY: &str = "untrusted comment: minisign public key 7DBB48F3E3A389F9
+RWT5iaPj80i7fWHbu5L6tJ5wt9wxx6h77SvMOMA2YhYnu8q/eSKvR774";
+    // signature of `OTHER_PUBKEY` created with the `SIGNER_PUBKEY` secret key
+    const SIGNER_OTHER_PUBKEY_SIGNATURE: &str =
+        "untrusted comment: signature from minisign secret key
+RWT5iaPj80i7fejWTZitDddqjQyJhHCRyik86XoRSqOYL3Bw/toDft48fOr/0HAoiuMJXf8y/yDB31L2bbK5ifuAA95sUtktDQg=
+trusted comment: timestamp:1735689600\tfile:pubkey
+1jtfMhiKZqIugYmxK5AAOb1uuu/vFKC13G+l7TdCgsBm0syuB2KVlF6+wr90ldM9ucACI1ExDufrxJP7FBv3AA==";
+    // signature of `test` created with the `SIGNER_PUBKEY` secret key
+    const SIGNER_TEST_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RWT5iaPj80i7feVYNe8/X7CAlsViK+WxX6180poLulKUhlearrtma3Kst+n3A2H+mSyaUJKVoOM2QTirtXa/0/H1LrQq9O2UKg8=
+trusted comment: timestamp:1735689600\tfile:test
+hzS6MEShuykDwK+EGJg06rFfgPuFYX1/KyvTUt/IgVNoTpQHEg8s/O0KL6xoBJSlYDNbYZrUAJE94dm+pSRkDw==";
+
+    fn base64(s: &str) -> String {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD.encode(s)
+    }
+
+    #[test]
+    fn it_verifies_signatures_with_any_trusted_key() {
+        use super::{decode_pubkeys, verify_signature};
+
+        let mut config = crate::Config {
+            pubkey: OTHER_PUBKEY.into(),
+            ..Default::default()
+        };
+        let signature = base64(TEST_SIGNATURE);
+
+        let public_keys = decode_pubkeys(&config).unwrap();
+        assert!(verify_signature(b"test", &signature, &public_keys).is_err());
+
+        config.pubkeys.push(base64(TEST_PUBKEY));
+        let public_keys = decode_pubkeys(&config).unwrap();
+        assert!(verify_signature(b"test", &signature, &public_keys).is_ok());
+        assert!(verify_signature(b"Test", &signature, &public_keys).is_err());
+
+        assert!(matches!(
+            decode_pubkeys(&crate::Config::default()),
+            Err(crate::Error::EmptyPubkeys)
+        ));
+    }
+
+    #[test]
+    fn it_trusts_pubkeys_announced_with_a_trusted_signature() {
+        use super::RemoteRelease;
+
+        let release: RemoteRelease = serde_json::from_value(serde_json::json!({
+            "version": "1.1.0",
+            "url": "https://example.com/app_1.1.0_amd64.AppImage",
+            "signature": "",
+            "pubkeys": [
+                { "pubkey": OTHER_PUBKEY, "signature": base64(SIGNER_OTHER_PUBKEY_SIGNATURE) },
+                // validly signed but not a public key
+                { "pubkey": "test", "signature": base64(SIGNER_TEST_SIGNATURE) }
+            ]
+        }))
+        .unwrap();
+
+        let config = crate::Config {
+            pubkey: base64(TEST_PUBKEY),
+            ..Default::default()
+        };
+        assert!(release.trusted_pubkeys(&config).unwrap().is_empty());
+
+        let mut config = crate::Config {
+            pubkey: base64(SIGNER_PUBKEY),
+            ..Default::default()
+        };
+        let trusted = release.trusted_pubkeys(&config).unwrap();
+        assert_eq!(trusted, vec![OTHER_PUBKEY]);
+
+        config.pubkeys.extend(trusted);
+        assert_eq!(super::decode_pubkeys(&config).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn it_parses_mirrors_and_checksums() {
+        use super::RemoteRelease;
+
+        let release: RemoteRelease = serde_json::from_value(serde_json::json!({
+            "version": "1.1.0",
+            "platforms": {
+                "linux-x86_64": {
+                    "url": "https://eu.example.com/app_1.1.0_amd64.AppImage",
+                    "signature": "",
+                    "mirrors": ["https://us.example.com/app_1.1.0_amd64.AppImage"],
+                    "sha256": "abcdef"
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            release.mirrors("linux-x86_64").unwrap()[0].as_str(),
+            "https://us.example.com/app_1.1.0_amd64.AppImage"
+        );
+        assert_eq!(
+            release.sha256("linux-x86_64").unwrap().map(String::as_str),
+            Some("abcdef")
+        );
+        assert!(release.mirrors("windows-x86_64").is_err());
+    }
+
+    #[test]
+    fn it_validates_checksum_before_signature() {
+        use super::DownloadVerifier;
+        use std::path::Path;
+
+        let mut verifier = DownloadVerifier::new(&[], None);
+        verifier.update(b"test");
+        let err = verifier
+            .verify(Path::new("not-read"), Some("0000"), "", &[])
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::ChecksumMismatch { actual, .. }
+            if actual == "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"));
+    }
+
+    /// Serves `test` as the update package, failing the first download after `fail_after` bytes.
+    #[derive(Clone, Default)]
+    struct FlakySource {
+        fail_after: std::sync::Arc<std::sync::Mutex<Option<usize>>>,
+        offsets: std::sync::Arc<std::sync::Mutex<Vec<u64>>>,
+    }
+
+    impl crate::UpdateSource for FlakySource {
+        fn fetch_manifest<'a>(
+            &'a self,
+            _url: &'a url::Url,
+            _headers: &'a http::HeaderMap,
+        ) -> futures_util::future::BoxFuture<
+            'a,
+            std::result::Result<Option<Vec<u8>>, crate::EndpointErrorKind>,
+        > {
+            use futures_util::FutureExt;
+
+            let manifest = serde_json::json!({
+                "version": "1.1.0",
+                "url": "https://example.com/app_1.1.0.tar.gz",
+                "signature": base64(TEST_SIGNATURE),
+            });
+            async move { Ok(Some(serde_json::to_vec(&manifest).unwrap())) }.boxed()
+        }
+
+        fn download<'a>(
+            &'a self,
+            _url: &'a url::Url,
+            _headers: &'a http::HeaderMap,
+            offset: u64,
+        ) -> futures_util::future::BoxFuture<'a, crate::Result<crate::PackageStream>> {
+            use futures_util::{FutureExt, StreamExt};
+
+            self.offsets.lock().unwrap().push(offset);
+            let rest = b"test"[offset as usize..].to_vec();
+            let fail_after = self.fail_after.lock().unwrap().take();
+            async move {
+                let chunks = match fail_after {
+                    Some(len) => {
+                        // a connection error, like the ones of an interrupted download
+                        let port = std::net::TcpListener::bind("127.0.0.1:0")
+                            .unwrap()
+                            .local_addr()
+                            .unwrap()
+                            .port();
+                        let error = reqwest::get(format!("http://127.0.0.1:{port}"))
+                            .await
+                            .unwrap_err();
+                        futures_util::stream::iter([Ok(rest[..len].to_vec()), Err(error.into())])
+                            .boxed()
+                    }
+                    None => futures_util::stream::iter([Ok(rest.clone())]).boxed(),
+                };
+                Ok(crate::PackageStream {
+                    resumed: offset > 0,
+                    content_length: Some(rest.len() as u64),
+                    chunks,
+                })
+            }
+            .boxed()
+        }
+    }
+
+    #[test]
+    fn it_resumes_and_retries_interrupted_downloads() {
+        use super::{decode_pubkeys, DownloadVerifier, UpdaterBuilder};
+
+        let config: crate::Config = serde_json::from_value(serde_json::json!({
+            "endpoints": ["https://example.com/latest.json"],
+            "pubkey": base64(TEST_PUBKEY),
+        }))
+        .unwrap();
+        let source = FlakySource::default();
+        source.fail_after.lock().unwrap().replace(1);
+        let updater = UpdaterBuilder::new("app".into(), semver::Version::new(1, 0, 0), config)
+            .target("test-target")
+            .source(source.clone())
+            .build()
+            .unwrap();
+        let update = tauri::async_runtime::block_on(updater.check())
+            .unwrap()
+            .unwrap();
+
+        // a previous download was interrupted after two bytes
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.part");
+        std::fs::write(&path, "te").unwrap();
+
+        let public_keys = decode_pubkeys(&update.config).unwrap();
+        let mut progress = Vec::new();
+        let verifier = tauri::async_runtime::block_on(update.download_with_retries(
+            &source,
+            &update.download_url,
+            &path,
+            &public_keys,
+            None,
+            &mut |len, total| progress.push((len, total)),
+        ))
+        .unwrap();
+
+        // resumed from the partial file, then from the byte received before the error
+        assert_eq!(*source.offsets.lock().unwrap(), [2, 3]);
+        assert_eq!(std::fs::read(&path).unwrap(), b"test");
+        // the bytes received before the error are reported once
+        assert_eq!(progress.iter().map(|(len, _)| len).sum::<usize>(), 4);
+        verifier
+            .verify(
+                &path,
+                Some("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"),
+                &update.signature,
+                &public_keys,
+            )
+            .unwrap();
+
+        // the file on disk is verified, not only the downloaded bytes
+        std::fs::write(&path, "Test").unwrap();
+        let mut verifier = DownloadVerifier::new(&public_keys, None);
+        verifier.update_from_file(&path).unwrap();
+        assert!(verifier
+            .verify(&path, None, &update.signature, &public_keys)
+            .is_err());
+    }
+
+    #[test]
+    fn it_parses_mandatory_releases() {
+        use super::RemoteRelease;
+        use semver::Version;
+
+        let release: RemoteRelease = serde_json::from_value(serde_json::json!({
+            "version": "1.3.0",
+            "url": "https://example.com/app_1.3.0_amd64.AppImage",
+            "signature": "",
+            "minimum_version": "v1.1.0",
+            "revoked_versions": ["1.2.0"]
+        }))
+        .unwrap();
+
+        assert!(!release.mandatory);
+        assert!(release.is_mandatory_for(&Version::new(1, 0, 0)));
+        assert!(!release.is_mandatory_for(&Version::new(1, 1, 0)));
+        assert!(release.is_mandatory_for(&Version::new(1, 2, 0)));
+        assert!(!release.is_mandatory_for(&Version::new(1, 2, 1)));
+
+        let release: RemoteRelease = serde_json::from_value(serde_json::json!({
+            "version": "1.3.0",
+            "url": "https://example.com/app_1.3.0_amd64.AppImage",
+            "signature": "",
+            "mandatory": true
+        }))
+        .unwrap();
+        assert!(release.is_mandatory_for(&Version::new(1, 2, 1)));
+    }
+
+    #[test]
+    #[cfg(all(feature = "delta", target_os = "linux"))]
+    fn it_applies_patches() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let old = b"the previous executable contents".repeat(100);
+        let new = b"the updated executable contents".repeat(100);
+
+        let mut encoder =
+            zstd::stream::write::Encoder::with_ref_prefix(Vec::new(), 19, &old).unwrap();
+        encoder.write_all(&new).unwrap();
+        let patch = encoder.finish().unwrap();
+
+        std::fs::write(dir.path().join("old"), &old).unwrap();
+        std::fs::write(dir.path().join("patch"), patch).unwrap();
+        super::apply_patch(
+            &dir.path().join("old"),
+            &dir.path().join("patch"),
+            &dir.path().join("new"),
+        )
+        .unwrap();
+
+        assert_eq!(std::fs::read(dir.path().join("new")).unwrap(), new);
+
+        assert_eq!(super::patch_window_log_max(old.len()), 27);
+        assert_eq!(super::patch_window_log_max(200 * 1024 * 1024), 29);
+    }
+
     #[test]
     #[cfg(windows)]
     fn it_wraps_correctly() {